@@ -1,6 +1,11 @@
+use core::fmt;
+
+#[cfg(feature = "zeroize")]
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
 use crate::ONES;
 
-#[derive(Clone, Copy, Debug, Default)]
+#[derive(Clone, Default)]
 pub struct State {
     s0: u64,
     s61: u64,
@@ -10,6 +15,34 @@ pub struct State {
     s230: u64,
 }
 
+impl fmt::Debug for State {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("State").finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Zeroize for State {
+    fn zeroize(&mut self) {
+        self.s0.zeroize();
+        self.s61.zeroize();
+        self.s107.zeroize();
+        self.s154.zeroize();
+        self.s193.zeroize();
+        self.s230.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for State {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl ZeroizeOnDrop for State {}
+
 pub fn maj(x: u32, y: u32, z: u32) -> u32 {
     (x & y) ^ (x & z) ^ (y & z)
 }
@@ -19,13 +52,16 @@ pub fn ch(x: u32, y: u32, z: u32) -> u32 {
 }
 
 #[inline]
-pub fn u32_from_be_bytes(input: &[u8]) -> u32 {
+pub fn u32_from_le_bytes(input: &[u8]) -> u32 {
     // Soundness: function is always called with slices of the correct size
-    u32::from_be_bytes(input.try_into().unwrap())
+    u32::from_le_bytes(input.try_into().unwrap())
 }
 
 impl State {
-    fn update8(&mut self, m: u32, ca: u32, cb: u32) -> u32 {
+    /// Clock the state 8 times and return the 8 keystream bits.
+    ///
+    /// `mode` is all ones when `m` holds ciphertext, so that the plaintext `m ^ ks` is absorbed.
+    fn update8(&mut self, m: u32, ca: u32, cb: u32, mode: u32) -> u32 {
         let s244 = (self.s230 >> 14) as u32;
         let s235 = (self.s230 >> 5) as u32;
         let s196 = (self.s193 >> 3) as u32;
@@ -38,16 +74,16 @@ impl State {
 
         let x289 = (s235 ^ (self.s230 as u32)) & 0xFF;
 
-        let s230 = (self.s230 as u32) ^ s196 ^ (self.s193 as u32) & 0xFF;
-        let s193 = (self.s193 as u32) ^ s160 ^ (self.s154 as u32) & 0xFF;
-        let s154 = (self.s154 as u32) ^ s111 ^ (self.s107 as u32) & 0xFF;
-        let s107 = (self.s107 as u32) ^ s66 ^ (self.s61 as u32) & 0xFF;
-        let s61 = (self.s61 as u32) ^ s23 ^ s0 & 0xFF;
+        let s230 = ((self.s230 as u32) ^ s196 ^ (self.s193 as u32)) & 0xFF;
+        let s193 = ((self.s193 as u32) ^ s160 ^ (self.s154 as u32)) & 0xFF;
+        let s154 = ((self.s154 as u32) ^ s111 ^ (self.s107 as u32)) & 0xFF;
+        let s107 = ((self.s107 as u32) ^ s66 ^ (self.s61 as u32)) & 0xFF;
+        let s61 = ((self.s61 as u32) ^ s23 ^ s0) & 0xFF;
 
-        let ks = s12 ^ s154 ^ maj(s235, s61, s193) ^ ch(s230, s111, s66) & 0xFF;
-        let f = s0 ^ !s107 ^ maj(s244, s23, s160) ^ (ca & s196) ^ (cb & ks) & 0xFF;
+        let ks = (s12 ^ s154 ^ maj(s235, s61, s193) ^ ch(s230, s111, s66)) & 0xFF;
+        let f = s0 ^ !s107 ^ maj(s244, s23, s160) ^ (ca & s196) ^ (cb & ks);
 
-        let s293 = (f ^ m) & 0xFF;
+        let s293 = (f ^ m ^ (ks & mode)) & 0xFF;
 
         self.s230 =
             self.s230 >> 8 ^ (x289 as u64) << (289 - 230 - 8) ^ (s293 as u64) << (293 - 230 - 8);
@@ -60,7 +96,10 @@ impl State {
         ks
     }
 
-    fn update32(&mut self, m: u32, ca: u32, cb: u32) -> u32 {
+    /// Clock the state 32 times and return the 32 keystream bits.
+    ///
+    /// `mode` is all ones when `m` holds ciphertext, so that the plaintext `m ^ ks` is absorbed.
+    fn update32(&mut self, m: u32, ca: u32, cb: u32, mode: u32) -> u32 {
         let s244 = (self.s230 >> 14) as u32;
         let s235 = (self.s230 >> 5) as u32;
         let s196 = (self.s193 >> 3) as u32;
@@ -82,7 +121,7 @@ impl State {
         let ks = s12 ^ s154 ^ maj(s235, s61, s193) ^ ch(s230, s111, s66);
         let f = s0 ^ !s107 ^ maj(s244, s23, s160) ^ (ca & s196) ^ (cb & ks);
 
-        let s293 = f ^ m;
+        let s293 = f ^ m ^ (ks & mode);
 
         self.s230 =
             self.s230 >> 32 ^ (x289 as u64) << (289 - 230 - 32) ^ (s293 as u64) << (293 - 230 - 32);
@@ -96,62 +135,93 @@ impl State {
     }
 
     pub fn init(&mut self, key: &[u32; 4], iv: &[u8]) {
-        for i in 0..4 {
-            self.update32(key[i], ONES, ONES);
+        for word in key {
+            self.update32(*word, ONES, ONES, 0);
         }
-        for i in 0..iv.len() {
-            self.update8(iv[i] as u32, ONES, ONES);
+        for byte in iv {
+            self.update8(*byte as u32, ONES, ONES, 0);
         }
-        self.update32(key[0] ^ 0x01, ONES, ONES);
-        for i in (32..15636).step_by(32) {
-            self.update32(key[i % 128 / 32], ONES, ONES);
+        self.update32(key[0] ^ 0x01, ONES, ONES, 0);
+        for i in (32..1536).step_by(32) {
+            self.update32(key[i % 128 / 32], ONES, ONES, 0);
         }
     }
 
-    fn pad(&mut self, cb: u32) {
-        self.update32(0x01, ONES, cb);
+    /// Clock the padding of the associated data (`cb` all ones) or of the message (`cb` zero).
+    pub fn pad(&mut self, cb: u32) {
+        self.update32(0x01, ONES, cb, 0);
         for _ in (32..128).step_by(32) {
-            self.update32(0x00, ONES, ONES);
+            self.update32(0x00, ONES, cb, 0);
         }
         for _ in (128..256).step_by(32) {
-            self.update32(0x00, 0, cb);
+            self.update32(0x00, 0, cb, 0);
         }
     }
 
-    pub fn process_associated_data(&mut self, ad: &[u8]) {
-        for i in 0..ad.len() {
-            self.update8(ad[i] as u32, ONES, ONES);
+    /// Absorb a fragment of the associated data, leaving out the padding.
+    pub fn absorb_associated_data(&mut self, ad: &[u8]) {
+        for byte in ad {
+            self.update8(*byte as u32, ONES, ONES, 0);
         }
+    }
+
+    pub fn process_associated_data(&mut self, ad: &[u8]) {
+        self.absorb_associated_data(ad);
         self.pad(ONES);
     }
 
-    pub fn crypt(&mut self, message: &mut [u8], mode: u32) {
+    /// Encrypt or decrypt a fragment of the message, leaving out the padding.
+    ///
+    /// ACORN clocks one byte at a time, so fragments of any length give the same result as the
+    /// whole message.
+    pub fn crypt_fragment(&mut self, message: &mut [u8], mode: u32) {
         let mut chunks = message.chunks_exact_mut(4);
         for blocks in chunks.by_ref() {
-            let cx = u32_from_be_bytes(blocks);
-            let ks = self.update32(cx, ONES, mode);
-            blocks.copy_from_slice(&(cx ^ ks).to_be_bytes());
+            let cx = u32_from_le_bytes(blocks);
+            let ks = self.update32(cx, ONES, 0, mode);
+            blocks.copy_from_slice(&(cx ^ ks).to_le_bytes());
         }
 
         let last_block = chunks.into_remainder();
         if !last_block.is_empty() {
             // Iterate over the last block and apply update8
-            for i in 0..last_block.len() {
-                let cx = last_block[i];
-                let ks = self.update8(cx as u32, ONES, mode) as u8;
-                last_block[i] = cx ^ ks;
+            for byte in last_block.iter_mut() {
+                let cx = *byte;
+                let ks = self.update8(cx as u32, ONES, 0, mode) as u8;
+                *byte = cx ^ ks;
             }
         }
+    }
+
+    pub fn crypt(&mut self, message: &mut [u8], mode: u32) {
+        self.crypt_fragment(message, mode);
         self.pad(0);
     }
 
     pub fn finalize(&mut self, tag: &mut [u8; 16]) {
         for _ in (0..640).step_by(32) {
-            self.update32(0x00, ONES, ONES);
+            self.update32(0x00, ONES, ONES, 0);
         }
-        for i in 0..tag.len() {
-            let ks = self.update8(0x00, ONES, ONES) as u8;
-            tag[i] = ks;
+        for byte in tag.iter_mut() {
+            *byte = self.update8(0x00, ONES, ONES, 0) as u8;
         }
     }
 }
+
+#[cfg(all(test, feature = "zeroize"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zeroize_state() {
+        let mut state = State::default();
+        state.init(&[0x0123_4567; 4], &[0x89; 16]);
+        assert_ne!(state.s0 | state.s230, 0);
+
+        state.zeroize();
+        assert_eq!(
+            [state.s0, state.s61, state.s107, state.s154, state.s193, state.s230],
+            [0; 6]
+        );
+    }
+}
@@ -1,6 +1,21 @@
-use acorn_core::u32_from_be_bytes;
+use core::fmt;
+
+use acorn_core::u32_from_le_bytes;
+use lwc_core::Aead;
+pub use lwc_core::Error;
+#[cfg(feature = "std")]
+pub use lwc_core::{DecryptReader, EncryptWriter};
+pub use stream::{AcornDecryptor, AcornEncryptor};
+#[cfg(feature = "zeroize")]
+use zeroize::{Zeroize, ZeroizeOnDrop};
 
 mod acorn_core;
+#[cfg(feature = "aead")]
+mod rustcrypto;
+mod stream;
+
+#[cfg(feature = "aead")]
+pub use aead;
 
 const ONES: u32 = 0xffff_ffff;
 
@@ -9,18 +24,33 @@ pub struct AcornHead {
 }
 
 impl AcornHead {
-    pub fn new(key: &[u8]) -> Self {
+    pub fn new(key: &[u8; 16]) -> Self {
         Self {
             key: [
-                u32_from_be_bytes(&key[0..4]),
-                u32_from_be_bytes(&key[4..8]),
-                u32_from_be_bytes(&key[8..12]),
-                u32_from_be_bytes(&key[12..16]),
+                u32_from_le_bytes(&key[0..4]),
+                u32_from_le_bytes(&key[4..8]),
+                u32_from_le_bytes(&key[8..12]),
+                u32_from_le_bytes(&key[12..16]),
             ],
         }
     }
 
-    pub fn encrypt(&self, pt: &mut [u8], ad: &[u8], nonce: &[u8]) -> [u8; 16] {
+    pub fn new_from_slice(key: &[u8]) -> Result<Self, Error> {
+        let key = key.try_into().map_err(|_| Error::InvalidKeyLength)?;
+        Ok(Self::new(key))
+    }
+
+    /// Start encrypting a message given in fragments.
+    pub fn encryptor(&self, nonce: &[u8; 16]) -> AcornEncryptor {
+        AcornEncryptor::new(&self.key, nonce)
+    }
+
+    /// Start decrypting a message given in fragments.
+    pub fn decryptor(&self, nonce: &[u8; 16]) -> AcornDecryptor {
+        AcornDecryptor::new(&self.key, nonce)
+    }
+
+    pub fn encrypt(&self, pt: &mut [u8], ad: &[u8], nonce: &[u8; 16]) -> [u8; 16] {
         let mut state = acorn_core::State::default();
         state.init(&self.key, nonce);
         state.process_associated_data(ad);
@@ -36,9 +66,13 @@ impl AcornHead {
         &self,
         ct: &mut [u8],
         ad: &[u8],
-        nonce: &[u8],
+        nonce: &[u8; 16],
         expected_tag: &[u8],
-    ) -> Result<(), ()> {
+    ) -> Result<(), Error> {
+        if expected_tag.len() != 16 {
+            return Err(Error::InvalidTagLength);
+        }
+
         let mut state = acorn_core::State::default();
         state.init(&self.key, nonce);
         state.process_associated_data(ad);
@@ -47,11 +81,64 @@ impl AcornHead {
         let mut tag = [0u8; 16];
         state.finalize(&mut tag);
 
-        if tag == expected_tag {
-            Ok(())
-        } else {
-            Err(())
-        }
+        lwc_core::verify_tag_or_wipe(&tag, expected_tag, ct)
+    }
+}
+
+impl fmt::Debug for AcornHead {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AcornHead").finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for AcornHead {
+    fn drop(&mut self) {
+        self.key.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl ZeroizeOnDrop for AcornHead {}
+
+impl TryFrom<&[u8]> for AcornHead {
+    type Error = Error;
+
+    fn try_from(key: &[u8]) -> Result<Self, Self::Error> {
+        Self::new_from_slice(key)
+    }
+}
+
+impl Aead for AcornHead {
+    const KEY_SIZE: usize = 16;
+    const NONCE_SIZE: usize = 16;
+    const TAG_SIZE: usize = 16;
+
+    type Tag = [u8; 16];
+
+    fn new_from_slice(key: &[u8]) -> Result<Self, Error> {
+        Self::new_from_slice(key)
+    }
+
+    fn encrypt_inplace_detached(
+        &self,
+        nonce: &[u8],
+        associated_data: &[u8],
+        buffer: &mut [u8],
+    ) -> Result<Self::Tag, Error> {
+        let nonce = nonce.try_into().map_err(|_| Error::InvalidNonceLength)?;
+        Ok(self.encrypt(buffer, associated_data, nonce))
+    }
+
+    fn decrypt_inplace_detached(
+        &self,
+        nonce: &[u8],
+        associated_data: &[u8],
+        buffer: &mut [u8],
+        tag: &[u8],
+    ) -> Result<(), Error> {
+        let nonce = nonce.try_into().map_err(|_| Error::InvalidNonceLength)?;
+        self.decrypt(buffer, associated_data, nonce, tag)
     }
 }
 
@@ -83,4 +170,71 @@ mod test {
         assert!(acorn.decrypt(&mut ct, ad, &NONCE, &tag).is_ok());
         assert_eq!([0u8; 16], ct);
     }
+
+    #[test]
+    fn acorn_128_aead() {
+        let acorn = AcornHead::new(&KEY);
+
+        let mut buffer = b"Hello, world!".to_vec();
+        acorn
+            .encrypt_inplace(&NONCE, b"header", &mut buffer)
+            .unwrap();
+        assert_eq!(buffer.len(), 13 + AcornHead::TAG_SIZE);
+
+        assert!(acorn
+            .decrypt_inplace(&NONCE, b"header", &mut buffer)
+            .is_ok());
+        assert_eq!(b"Hello, world!", buffer.as_slice());
+    }
+
+    #[test]
+    fn acorn_128_forged_tag() {
+        let acorn = AcornHead::new(&KEY);
+
+        let mut buffer = *b"Hello, world!";
+        let mut tag = acorn.encrypt(&mut buffer, b"", &NONCE);
+        let ciphertext = buffer;
+        assert_eq!(
+            acorn.decrypt(&mut buffer, b"", &NONCE, &tag[..15]),
+            Err(Error::InvalidTagLength)
+        );
+        assert_eq!(buffer, ciphertext);
+
+        tag[0] ^= 1;
+        assert_eq!(
+            acorn.decrypt(&mut buffer, b"", &NONCE, &tag),
+            Err(Error::AuthenticationFailed)
+        );
+        assert!(buffer.iter().all(|&byte| byte == 0));
+    }
+
+    #[test]
+    fn acorn_128_lengths() {
+        assert_eq!(
+            AcornHead::new_from_slice(&KEY[..15]).err(),
+            Some(Error::InvalidKeyLength)
+        );
+        assert_eq!(
+            AcornHead::try_from([0u8; 17].as_ref()).err(),
+            Some(Error::InvalidKeyLength)
+        );
+
+        let acorn = AcornHead::new_from_slice(&KEY).unwrap();
+        let mut buffer = [0u8; 16];
+        assert_eq!(
+            acorn.encrypt_inplace_detached(&NONCE[..12], b"", &mut buffer),
+            Err(Error::InvalidNonceLength)
+        );
+        assert_eq!(
+            acorn.decrypt_inplace_detached(&NONCE[..12], b"", &mut buffer, &[0u8; 16]),
+            Err(Error::InvalidNonceLength)
+        );
+    }
+
+    #[test]
+    fn acorn_128_debug_is_redacted() {
+        let acorn = AcornHead::new(&[0xab; 16]);
+        let debug = format!("{acorn:?}");
+        assert_eq!(debug, "AcornHead { .. }");
+    }
 }
@@ -0,0 +1,83 @@
+use aead::consts::{U0, U16};
+use aead::{AeadCore, AeadInPlace, Key, KeyInit, KeySizeUser, Nonce, Tag};
+
+use crate::AcornHead;
+
+impl KeySizeUser for AcornHead {
+    type KeySize = U16;
+}
+
+impl KeyInit for AcornHead {
+    fn new(key: &Key<Self>) -> Self {
+        AcornHead::new(key.as_ref())
+    }
+}
+
+impl AeadCore for AcornHead {
+    type NonceSize = U16;
+    type TagSize = U16;
+    type CiphertextOverhead = U0;
+}
+
+impl AeadInPlace for AcornHead {
+    fn encrypt_in_place_detached(
+        &self,
+        nonce: &Nonce<Self>,
+        associated_data: &[u8],
+        buffer: &mut [u8],
+    ) -> aead::Result<Tag<Self>> {
+        Ok(self.encrypt(buffer, associated_data, nonce.as_ref()).into())
+    }
+
+    fn decrypt_in_place_detached(
+        &self,
+        nonce: &Nonce<Self>,
+        associated_data: &[u8],
+        buffer: &mut [u8],
+        tag: &Tag<Self>,
+    ) -> aead::Result<()> {
+        self.decrypt(buffer, associated_data, nonce.as_ref(), tag)
+            .map_err(|_| aead::Error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aead::{Aead, Payload};
+
+    use super::*;
+
+    const KEY: [u8; 16] = [
+        0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e,
+        0x0f,
+    ];
+
+    const NONCE: [u8; 16] = [
+        0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18, 0x19, 0x1a, 0x1b, 0x1c, 0x1d, 0x1e,
+        0x1f,
+    ];
+
+    #[test]
+    fn rustcrypto_aead() {
+        let cipher = <AcornHead as KeyInit>::new(&KEY.into());
+        let nonce = Nonce::<AcornHead>::from(NONCE);
+        let payload = Payload {
+            msg: b"Hello, world!",
+            aad: b"header",
+        };
+
+        let ciphertext = Aead::encrypt(&cipher, &nonce, payload).unwrap();
+
+        let mut expected = b"Hello, world!".to_vec();
+        let tag = AcornHead::new(&KEY).encrypt(&mut expected, b"header", &NONCE);
+        expected.extend_from_slice(&tag);
+        assert_eq!(ciphertext, expected);
+
+        let payload = Payload {
+            msg: &ciphertext,
+            aad: b"header",
+        };
+        let plaintext = Aead::decrypt(&cipher, &nonce, payload).unwrap();
+        assert_eq!(plaintext, b"Hello, world!");
+    }
+}
@@ -0,0 +1,111 @@
+/*
+ * Generator of acorn128.txt: a bit-serial transcription of ACORN-128 v3 as specified in the CAESAR
+ * submission (acorn128v3, Hongjun Wu, 2016) and its reference implementation, driven the way the
+ * CAESAR genkat_aead.c program drives crypto_aead_encrypt.
+ *
+ * The state is kept one bit per byte and updated one step at a time, exactly as in the
+ * specification, so it shares no code or word layout with the 32-bit implementation of the crate.
+ *
+ *     cc -O1 -o genkat_acorn128 genkat_acorn128.c
+ *     ./genkat_acorn128 > acorn128.txt
+ */
+
+#include <stdio.h>
+#include <string.h>
+
+#define MAX_MESSAGE_LENGTH 32
+#define MAX_ASSOCIATED_DATA_LENGTH 32
+
+static unsigned char maj(unsigned char x, unsigned char y, unsigned char z) {
+    return (x & y) ^ (x & z) ^ (y & z);
+}
+
+static unsigned char ch(unsigned char x, unsigned char y, unsigned char z) {
+    return (x & y) ^ ((x ^ 1) & z);
+}
+
+/* One step of the state update, returning the keystream bit. */
+static unsigned char step(unsigned char state[293], unsigned char m, unsigned char ca,
+                          unsigned char cb) {
+    unsigned char ks, f;
+
+    state[289] ^= state[235] ^ state[230];
+    state[230] ^= state[196] ^ state[193];
+    state[193] ^= state[160] ^ state[154];
+    state[154] ^= state[111] ^ state[107];
+    state[107] ^= state[66] ^ state[61];
+    state[61] ^= state[23] ^ state[0];
+
+    ks = state[12] ^ state[154] ^ maj(state[235], state[61], state[193]) ^
+         ch(state[230], state[111], state[66]);
+    f = state[0] ^ (state[107] ^ 1) ^ maj(state[244], state[23], state[160]) ^
+        (ca & state[196]) ^ (cb & ks);
+
+    memmove(state, state + 1, 292);
+    state[292] = f ^ m;
+    return ks;
+}
+
+static unsigned char bit(const unsigned char *bytes, size_t i) {
+    return (bytes[i / 8] >> (i % 8)) & 1;
+}
+
+/* Absorb the 256 padding bits that end the associated data or the message. */
+static void pad(unsigned char state[293], unsigned char cb) {
+    for (int i = 0; i < 256; i++) step(state, i == 0, i < 128, cb);
+}
+
+static void encrypt(unsigned char *c, const unsigned char *m, size_t mlen, const unsigned char *ad,
+                    size_t adlen, const unsigned char *npub, const unsigned char *k) {
+    unsigned char state[293] = {0};
+
+    for (size_t i = 0; i < 128; i++) step(state, bit(k, i), 1, 1);
+    for (size_t i = 0; i < 128; i++) step(state, bit(npub, i), 1, 1);
+    for (size_t i = 0; i < 1536; i++) step(state, bit(k, i % 128) ^ (i == 0), 1, 1);
+
+    for (size_t i = 0; i < adlen * 8; i++) step(state, bit(ad, i), 1, 1);
+    pad(state, 1);
+
+    memset(c, 0, mlen + 16);
+    for (size_t i = 0; i < mlen * 8; i++) {
+        unsigned char p = bit(m, i);
+        c[i / 8] |= (step(state, p, 1, 0) ^ p) << (i % 8);
+    }
+    pad(state, 0);
+
+    for (size_t i = 0; i < 768; i++) {
+        unsigned char ks = step(state, 0, 1, 1);
+        if (i >= 640) c[mlen + (i - 640) / 8] |= ks << (i % 8);
+    }
+}
+
+static void print_hex(const char *name, const unsigned char *data, size_t len) {
+    printf("%s = ", name);
+    for (size_t i = 0; i < len; i++) printf("%02X", data[i]);
+    printf("\n");
+}
+
+int main(void) {
+    unsigned char key[16], nonce[16], msg[MAX_MESSAGE_LENGTH], ad[MAX_ASSOCIATED_DATA_LENGTH];
+    unsigned char ct[MAX_MESSAGE_LENGTH + 16];
+    int count = 1;
+
+    for (int i = 0; i < 16; i++) key[i] = nonce[i] = i;
+    for (int i = 0; i < MAX_MESSAGE_LENGTH; i++) msg[i] = i;
+    for (int i = 0; i < MAX_ASSOCIATED_DATA_LENGTH; i++) ad[i] = i;
+
+    for (size_t mlen = 0; mlen <= MAX_MESSAGE_LENGTH; mlen++) {
+        for (size_t adlen = 0; adlen <= MAX_ASSOCIATED_DATA_LENGTH; adlen++) {
+            encrypt(ct, msg, mlen, ad, adlen, nonce, key);
+
+            printf("Count = %d\n", count++);
+            print_hex("Key", key, 16);
+            print_hex("Nonce", nonce, 16);
+            print_hex("PT", msg, mlen);
+            print_hex("AD", ad, adlen);
+            print_hex("CT", ct, mlen + 16);
+            printf("\n");
+        }
+    }
+    return 0;
+}
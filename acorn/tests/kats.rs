@@ -0,0 +1,79 @@
+//! Known-answer tests for ACORN-128 v3.
+//!
+//! The vectors follow the layout of the CAESAR `genkat_aead.c` program: key and nonce counting up
+//! from zero, and every combination of 0 to 32 bytes of plaintext and associated data, so AD-only,
+//! PT-only and every length that ends in a partial 32-bit word are covered. They are generated by
+//! `data/genkat_acorn128.c`, a bit-serial transcription of the specification and of the
+//! submission's reference implementation (`acorn128v3/ref`) that shares no code with the crate.
+
+use acorn::AcornHead;
+use lwc_core::{kat, Aead};
+
+fn run_kats<A: Aead>(input: &str, expected_count: usize) {
+    let vectors = kat::parse(input);
+    assert_eq!(vectors.len(), expected_count);
+
+    for vector in vectors {
+        let cipher = A::new_from_slice(&vector.bytes("Key")).unwrap();
+        let nonce = vector.bytes("Nonce");
+        let associated_data = vector.bytes("AD");
+        let plaintext = vector.bytes("PT");
+        let ciphertext = vector.bytes("CT");
+
+        let mut buffer = plaintext.clone();
+        cipher
+            .encrypt_inplace(&nonce, &associated_data, &mut buffer)
+            .unwrap();
+        assert_eq!(
+            buffer,
+            ciphertext,
+            "encryption of vector {}",
+            vector.count()
+        );
+
+        cipher
+            .decrypt_inplace(&nonce, &associated_data, &mut buffer)
+            .unwrap_or_else(|err| panic!("decryption of vector {}: {err}", vector.count()));
+        assert_eq!(buffer, plaintext, "decryption of vector {}", vector.count());
+    }
+}
+
+#[test]
+fn acorn128() {
+    run_kats::<AcornHead>(include_str!("data/acorn128.txt"), 1089);
+}
+
+#[test]
+fn acorn128_streaming() {
+    // Feed the associated data and the message in uneven fragments.
+    for vector in kat::parse(include_str!("data/acorn128.txt")) {
+        let count = vector.count();
+        let cipher = AcornHead::new(&vector.bytes("Key").try_into().unwrap());
+        let nonce = vector.bytes("Nonce").try_into().unwrap();
+        let associated_data = vector.bytes("AD");
+        let plaintext = vector.bytes("PT");
+        let expected = vector.bytes("CT");
+        let (ciphertext, tag) = expected.split_at(plaintext.len());
+
+        let mut encryptor = cipher.encryptor(&nonce);
+        for chunk in associated_data.chunks(3) {
+            encryptor.update_ad(chunk).unwrap();
+        }
+        let mut buffer = plaintext.clone();
+        for chunk in buffer.chunks_mut(5) {
+            encryptor.update(chunk);
+        }
+        assert_eq!(buffer, ciphertext, "encryption of vector {count}");
+        assert_eq!(encryptor.finalize()[..], *tag, "tag of vector {count}");
+
+        let mut decryptor = cipher.decryptor(&nonce);
+        for chunk in associated_data.chunks(7) {
+            decryptor.update_ad(chunk).unwrap();
+        }
+        for chunk in buffer.chunks_mut(11) {
+            decryptor.update(chunk);
+        }
+        assert_eq!(buffer, plaintext, "decryption of vector {count}");
+        assert_eq!(decryptor.finalize(tag), Ok(()), "tag of vector {count}");
+    }
+}
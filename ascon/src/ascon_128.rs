@@ -1,44 +1,68 @@
-use alloc::vec::Vec;
+use core::fmt;
 
-use crate::ascon_core::{pad, State};
+#[cfg(feature = "zeroize")]
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::ascon_core::{pad, ByteOrder, State};
+use crate::Error;
 
 #[inline]
-fn u64_from_be_bytes(input: &[u8]) -> u64 {
+pub(crate) fn u64_from_be_bytes(input: &[u8]) -> u64 {
     u64::from_be_bytes(input.try_into().unwrap())
 }
 
 #[inline]
-fn u64_from_be_bytes_partial(input: &[u8]) -> u64 {
+pub(crate) fn u64_from_be_bytes_partial(input: &[u8]) -> u64 {
     let mut tmp = [0u8; 8];
     tmp[0..input.len()].copy_from_slice(input);
     u64::from_be_bytes(tmp)
 }
 
-#[inline(always)]
-const fn clear(word: u64, n: usize) -> u64 {
-    word & (0x00ffffffffffffff >> (n * 8 - 8))
-}
-
-const RATE: usize = 8;
+pub(crate) const RATE: usize = 8;
+pub(crate) const ROUNDS: usize = 6;
 const IV: u64 = 0x80400c0600000000;
 
-#[derive(Clone, Copy)]
+#[derive(Clone)]
 pub struct Key {
     k1: u64,
     k2: u64,
 }
 
 impl Key {
-    fn get_k1(&self) -> u64 {
+    pub(crate) fn get_k1(&self) -> u64 {
         self.k1
     }
-    fn get_k2(&self) -> u64 {
+    pub(crate) fn get_k2(&self) -> u64 {
         self.k2
     }
 }
 
-impl From<&[u8]> for Key {
-    fn from(key: &[u8]) -> Self {
+impl fmt::Debug for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Key").finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Zeroize for Key {
+    fn zeroize(&mut self) {
+        self.k1.zeroize();
+        self.k2.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for Key {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl ZeroizeOnDrop for Key {}
+
+impl From<&[u8; 16]> for Key {
+    fn from(key: &[u8; 16]) -> Self {
         Self {
             k1: u64_from_be_bytes(&key[..8]),
             k2: u64_from_be_bytes(&key[8..]),
@@ -46,13 +70,205 @@ impl From<&[u8]> for Key {
     }
 }
 
+impl TryFrom<&[u8]> for Key {
+    type Error = Error;
+
+    fn try_from(key: &[u8]) -> Result<Self, Self::Error> {
+        let key: &[u8; 16] = key.try_into().map_err(|_| Error::InvalidKeyLength)?;
+        Ok(Self::from(key))
+    }
+}
+
+/// XOR full words into the rate.
+fn absorb_words(state: &mut State, order: ByteOrder, words: &[u8]) {
+    for (i, word) in words.chunks_exact(8).enumerate() {
+        state[i] ^= order.load(word);
+    }
+}
+
+/// Encrypt full words in place, leaving the ciphertext in the rate.
+fn encrypt_words(state: &mut State, order: ByteOrder, words: &mut [u8]) {
+    for (i, word) in words.chunks_exact_mut(8).enumerate() {
+        state[i] ^= order.load(word);
+        word.copy_from_slice(&order.store(state[i]));
+    }
+}
+
+/// Decrypt full words in place, leaving the ciphertext in the rate.
+fn decrypt_words(state: &mut State, order: ByteOrder, words: &mut [u8]) {
+    for (i, word) in words.chunks_exact_mut(8).enumerate() {
+        let cx = order.load(word);
+        word.copy_from_slice(&order.store(state[i] ^ cx));
+        state[i] = cx;
+    }
+}
+
+/// Absorb the associated data, `rate` bytes at a time with `rounds` rounds of the permutation
+/// between blocks.
+///
+/// Ascon-128 and Ascon-80pq use a rate of 8 bytes and 6 rounds, Ascon-128a and Ascon-AEAD128 a
+/// rate of 16 bytes and 8 rounds. Ascon-AEAD128 also loads its words in little-endian order.
+pub(crate) fn process_associated_data(
+    state: &mut State,
+    order: ByteOrder,
+    rate: usize,
+    rounds: usize,
+    associated_data: &[u8],
+) {
+    if !associated_data.is_empty() {
+        let mut blocks = associated_data.chunks_exact(rate);
+        for block in blocks.by_ref() {
+            absorb_words(state, order, block);
+            state.permute_n(rounds);
+        }
+
+        let last_block = blocks.remainder();
+        let (words, last_word) = last_block.split_at(last_block.len() / 8 * 8);
+        absorb_words(state, order, words);
+        state[words.len() / 8] ^= order.load_padded(last_word);
+
+        state.permute_n(rounds);
+    }
+
+    state[4] ^= order.domain_separation();
+}
+
+/// Encrypt in place, `rate` bytes at a time with `rounds` rounds of the permutation between
+/// blocks.
+pub(crate) fn process_encrypt_inplace(
+    state: &mut State,
+    order: ByteOrder,
+    rate: usize,
+    rounds: usize,
+    message: &mut [u8],
+) {
+    let mut blocks = message.chunks_exact_mut(rate);
+    for block in blocks.by_ref() {
+        encrypt_words(state, order, block);
+        state.permute_n(rounds);
+    }
+
+    let last_block = blocks.into_remainder();
+    let (words, last_word) = last_block.split_at_mut(last_block.len() / 8 * 8);
+    encrypt_words(state, order, words);
+    let index = words.len() / 8;
+    state[index] ^= order.load_padded(last_word);
+    if !last_word.is_empty() {
+        last_word.copy_from_slice(&order.store(state[index])[0..last_word.len()]);
+    }
+}
+
+/// Decrypt in place, `rate` bytes at a time with `rounds` rounds of the permutation between
+/// blocks.
+pub(crate) fn process_decrypt_inplace(
+    state: &mut State,
+    order: ByteOrder,
+    rate: usize,
+    rounds: usize,
+    ciphertext: &mut [u8],
+) {
+    let mut blocks = ciphertext.chunks_exact_mut(rate);
+    for block in blocks.by_ref() {
+        decrypt_words(state, order, block);
+        state.permute_n(rounds);
+    }
+
+    let last_block = blocks.into_remainder();
+    let (words, last_word) = last_block.split_at_mut(last_block.len() / 8 * 8);
+    decrypt_words(state, order, words);
+    let index = words.len() / 8;
+    state[index] ^= order.pad(last_word.len());
+    if !last_word.is_empty() {
+        let cx = order.load_partial(last_word);
+        state[index] ^= cx;
+        last_word.copy_from_slice(&order.store(state[index])[0..last_word.len()]);
+        state[index] = order.clear(state[index], last_word.len()) ^ cx;
+    }
+}
+
+/// Absorb a fragment of associated data that starts `position` bytes into the current block, and
+/// return the position after it.
+///
+/// Every completed block is permuted right away: the last block is always a partial one, padded by
+/// [`finish_associated_data`].
+fn absorb_fragment(state: &mut State, mut position: usize, mut data: &[u8]) -> usize {
+    while !data.is_empty() {
+        if position == 0 && data.len() >= RATE {
+            state[0] ^= u64_from_be_bytes(&data[..RATE]);
+            state.permute_6();
+            data = &data[RATE..];
+            continue;
+        }
+
+        state[0] ^= (data[0] as u64) << (56 - 8 * position);
+        position += 1;
+        if position == RATE {
+            state.permute_6();
+            position = 0;
+        }
+        data = &data[1..];
+    }
+    position
+}
+
+/// Pad associated data given with [`absorb_fragment`] and separate it from the message.
+fn finish_associated_data(state: &mut State, position: usize, empty: bool) {
+    if !empty {
+        state[0] ^= pad(position);
+        state.permute_6();
+    }
+
+    state[4] ^= 1;
+}
+
+/// Encrypt or decrypt in place a fragment of the message that starts `position` bytes into the
+/// current block, and return the position after it.
+fn crypt_fragment(
+    state: &mut State,
+    mut position: usize,
+    mut data: &mut [u8],
+    decrypt: bool,
+) -> usize {
+    while !data.is_empty() {
+        if position == 0 && data.len() >= RATE {
+            let (block, rest) = core::mem::take(&mut data).split_at_mut(RATE);
+            if decrypt {
+                decrypt_words(state, ByteOrder::Big, block);
+            } else {
+                encrypt_words(state, ByteOrder::Big, block);
+            }
+            state.permute_6();
+            data = rest;
+            continue;
+        }
+
+        // The rate byte ends up holding the ciphertext byte whatever the direction.
+        let shift = 56 - 8 * position;
+        let keystream = (state[0] >> shift) as u8;
+        let plaintext = if decrypt {
+            keystream ^ data[0]
+        } else {
+            data[0]
+        };
+        state[0] ^= (plaintext as u64) << shift;
+        data[0] ^= keystream;
+        position += 1;
+        if position == RATE {
+            state.permute_6();
+            position = 0;
+        }
+        data = &mut data[1..];
+    }
+    position
+}
+
 pub struct Ascon128 {
     state: State,
     key: Key,
 }
 
 impl Ascon128 {
-    pub fn new(key: Key, nonce: &[u8]) -> Self {
+    pub fn new(key: &Key, nonce: &[u8; 16]) -> Self {
         let mut state = State::new(
             IV,
             key.get_k1(),
@@ -65,7 +281,10 @@ impl Ascon128 {
         state[3] ^= key.get_k1();
         state[4] ^= key.get_k2();
 
-        Self { state, key }
+        Self {
+            state,
+            key: key.clone(),
+        }
     }
 
     fn permute_12_and_apply_key(&mut self) {
@@ -74,63 +293,29 @@ impl Ascon128 {
         self.state[4] ^= self.key.get_k2();
     }
 
-    fn permute_state(&mut self) {
-        self.state.permute_6();
+    /// Absorb a fragment of associated data, see [`absorb_fragment`].
+    pub(crate) fn absorb_associated_data_fragment(&mut self, position: usize, ad: &[u8]) -> usize {
+        absorb_fragment(&mut self.state, position, ad)
     }
 
-    fn process_associated_date(&mut self, associated_data: &[u8]) {
-        if !associated_data.is_empty() {
-            let mut blocks = associated_data.chunks_exact(RATE);
-            for block in blocks.by_ref() {
-                self.state[0] ^= u64_from_be_bytes(block);
-            }
-            self.permute_state();
-
-            let last_block = blocks.remainder();
-            self.state[0] ^= pad(last_block.len());
-            if !last_block.is_empty() {
-                self.state[0] ^= u64_from_be_bytes_partial(last_block);
-            }
-
-            self.permute_state();
-        }
-
-        self.state[4] ^= 1;
+    pub(crate) fn finish_associated_data(&mut self, position: usize, empty: bool) {
+        finish_associated_data(&mut self.state, position, empty);
     }
 
-    fn process_encrypt_inplace(&mut self, message: &mut [u8]) {
-        let mut blocks = message.chunks_exact_mut(RATE);
-        for block in blocks.by_ref() {
-            self.state[0] ^= u64_from_be_bytes(&block);
-            block.copy_from_slice(&u64::to_be_bytes(self.state[0]));
-            self.permute_state();
-        }
-
-        let last_block = blocks.into_remainder();
-        self.state[0] ^= pad(last_block.len());
-        if !last_block.is_empty() {
-            self.state[0] ^= u64_from_be_bytes_partial(last_block);
-            last_block.copy_from_slice(&u64::to_be_bytes(self.state[0])[0..last_block.len()]);
-        }
+    /// Encrypt or decrypt a fragment of the message, see [`crypt_fragment`].
+    pub(crate) fn crypt_fragment(
+        &mut self,
+        position: usize,
+        buffer: &mut [u8],
+        decrypt: bool,
+    ) -> usize {
+        crypt_fragment(&mut self.state, position, buffer, decrypt)
     }
 
-    fn process_decrypt_inplace(&mut self, ciphertext: &mut [u8]) {
-        let mut blocks = ciphertext.chunks_exact_mut(RATE);
-        for block in blocks.by_ref() {
-            let cx = u64_from_be_bytes(&block);
-            block.copy_from_slice(&u64::to_be_bytes(self.state[0] ^ cx));
-            self.state[0] = cx;
-            self.permute_state();
-        }
-
-        let last_block = blocks.into_remainder();
-        self.state[0] ^= pad(last_block.len());
-        if !last_block.is_empty() {
-            let cx = u64_from_be_bytes_partial(last_block);
-            self.state[0] ^= cx;
-            last_block.copy_from_slice(&u64::to_be_bytes(self.state[0])[0..last_block.len()]);
-            self.state[0] = clear(self.state[0], last_block.len()) ^ cx;
-        }
+    /// Pad the message whose last block holds `position` bytes and return the tag.
+    pub(crate) fn finish_message(&mut self, position: usize) -> [u8; 16] {
+        self.state[0] ^= pad(position);
+        self.process_final()
     }
 
     fn process_final(&mut self) -> [u8; 16] {
@@ -145,10 +330,16 @@ impl Ascon128 {
         tag
     }
 
-    pub fn encrypt(&mut self, associated_data: &[u8], plaintext: &mut [u8]) -> Vec<u8> {
-        self.process_associated_date(associated_data);
-        self.process_encrypt_inplace(plaintext);
-        self.process_final().to_vec()
+    pub fn encrypt(&mut self, associated_data: &[u8], plaintext: &mut [u8]) -> [u8; 16] {
+        process_associated_data(
+            &mut self.state,
+            ByteOrder::Big,
+            RATE,
+            ROUNDS,
+            associated_data,
+        );
+        process_encrypt_inplace(&mut self.state, ByteOrder::Big, RATE, ROUNDS, plaintext);
+        self.process_final()
     }
 
     pub fn decrypt(
@@ -156,15 +347,21 @@ impl Ascon128 {
         associated_data: &[u8],
         ciphertext: &mut [u8],
         expected_tag: &[u8],
-    ) -> Result<(), ()> {
-        self.process_associated_date(associated_data);
-        self.process_decrypt_inplace(ciphertext);
+    ) -> Result<(), Error> {
+        if expected_tag.len() != 16 {
+            return Err(Error::InvalidTagLength);
+        }
+
+        process_associated_data(
+            &mut self.state,
+            ByteOrder::Big,
+            RATE,
+            ROUNDS,
+            associated_data,
+        );
+        process_decrypt_inplace(&mut self.state, ByteOrder::Big, RATE, ROUNDS, ciphertext);
         let tag = self.process_final();
 
-        if tag == *expected_tag {
-            Ok(())
-        } else {
-            Err(())
-        }
+        lwc_core::verify_tag_or_wipe(&tag, expected_tag, ciphertext)
     }
 }
@@ -0,0 +1,91 @@
+use crate::ascon_128::{
+    process_associated_data, process_decrypt_inplace, process_encrypt_inplace, u64_from_be_bytes,
+    Key,
+};
+use crate::ascon_core::{ByteOrder, State};
+use crate::Error;
+
+const RATE: usize = 16;
+const ROUNDS: usize = 8;
+const IV: u64 = 0x80800c0800000000;
+
+/// Ascon-128a: same key, nonce and tag as Ascon-128, but a 128-bit rate and 8 rounds between
+/// blocks.
+pub struct Ascon128a {
+    state: State,
+    key: Key,
+}
+
+impl Ascon128a {
+    pub fn new(key: &Key, nonce: &[u8; 16]) -> Self {
+        let mut state = State::new(
+            IV,
+            key.get_k1(),
+            key.get_k2(),
+            u64_from_be_bytes(&nonce[..8]),
+            u64_from_be_bytes(&nonce[8..]),
+        );
+
+        state.permute_12();
+        state[3] ^= key.get_k1();
+        state[4] ^= key.get_k2();
+
+        Self {
+            state,
+            key: key.clone(),
+        }
+    }
+
+    fn permute_12_and_apply_key(&mut self) {
+        self.state.permute_12();
+        self.state[3] ^= self.key.get_k1();
+        self.state[4] ^= self.key.get_k2();
+    }
+
+    fn process_final(&mut self) -> [u8; 16] {
+        self.state[2] ^= self.key.get_k1();
+        self.state[3] ^= self.key.get_k2();
+
+        self.permute_12_and_apply_key();
+
+        let mut tag = [0u8; 16];
+        tag[..8].copy_from_slice(&self.state[3].to_be_bytes());
+        tag[8..].copy_from_slice(&self.state[4].to_be_bytes());
+        tag
+    }
+
+    pub fn encrypt(&mut self, associated_data: &[u8], plaintext: &mut [u8]) -> [u8; 16] {
+        process_associated_data(
+            &mut self.state,
+            ByteOrder::Big,
+            RATE,
+            ROUNDS,
+            associated_data,
+        );
+        process_encrypt_inplace(&mut self.state, ByteOrder::Big, RATE, ROUNDS, plaintext);
+        self.process_final()
+    }
+
+    pub fn decrypt(
+        &mut self,
+        associated_data: &[u8],
+        ciphertext: &mut [u8],
+        expected_tag: &[u8],
+    ) -> Result<(), Error> {
+        if expected_tag.len() != 16 {
+            return Err(Error::InvalidTagLength);
+        }
+
+        process_associated_data(
+            &mut self.state,
+            ByteOrder::Big,
+            RATE,
+            ROUNDS,
+            associated_data,
+        );
+        process_decrypt_inplace(&mut self.state, ByteOrder::Big, RATE, ROUNDS, ciphertext);
+        let tag = self.process_final();
+
+        lwc_core::verify_tag_or_wipe(&tag, expected_tag, ciphertext)
+    }
+}
@@ -0,0 +1,146 @@
+use core::fmt;
+
+#[cfg(feature = "zeroize")]
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::ascon_128::{
+    process_associated_data, process_decrypt_inplace, process_encrypt_inplace, u64_from_be_bytes,
+    RATE, ROUNDS,
+};
+use crate::ascon_core::{ByteOrder, State};
+use crate::Error;
+
+const IV: u64 = 0xa0400c0600000000;
+
+/// 160-bit key of Ascon-80pq.
+///
+/// The first 32 bits are kept in the low half of `k0`, the rest in two 64-bit words.
+#[derive(Clone)]
+pub struct Key80pq {
+    k0: u64,
+    k1: u64,
+    k2: u64,
+}
+
+impl fmt::Debug for Key80pq {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Key80pq").finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Zeroize for Key80pq {
+    fn zeroize(&mut self) {
+        self.k0.zeroize();
+        self.k1.zeroize();
+        self.k2.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for Key80pq {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl ZeroizeOnDrop for Key80pq {}
+
+impl From<&[u8; 20]> for Key80pq {
+    fn from(key: &[u8; 20]) -> Self {
+        Self {
+            k0: u32::from_be_bytes(key[..4].try_into().unwrap()) as u64,
+            k1: u64_from_be_bytes(&key[4..12]),
+            k2: u64_from_be_bytes(&key[12..]),
+        }
+    }
+}
+
+impl TryFrom<&[u8]> for Key80pq {
+    type Error = Error;
+
+    fn try_from(key: &[u8]) -> Result<Self, Self::Error> {
+        let key: &[u8; 20] = key.try_into().map_err(|_| Error::InvalidKeyLength)?;
+        Ok(Self::from(key))
+    }
+}
+
+/// Ascon-80pq: Ascon-128 with a 160-bit key, whose extra 32 bits are mixed into the state during
+/// initialization and finalization.
+pub struct Ascon80pq {
+    state: State,
+    key: Key80pq,
+}
+
+impl Ascon80pq {
+    pub fn new(key: &Key80pq, nonce: &[u8; 16]) -> Self {
+        let mut state = State::new(
+            IV | key.k0,
+            key.k1,
+            key.k2,
+            u64_from_be_bytes(&nonce[..8]),
+            u64_from_be_bytes(&nonce[8..]),
+        );
+
+        state.permute_12();
+        state[2] ^= key.k0;
+        state[3] ^= key.k1;
+        state[4] ^= key.k2;
+
+        Self {
+            state,
+            key: key.clone(),
+        }
+    }
+
+    fn process_final(&mut self) -> [u8; 16] {
+        self.state[1] ^= self.key.k0 << 32 | self.key.k1 >> 32;
+        self.state[2] ^= self.key.k1 << 32 | self.key.k2 >> 32;
+        self.state[3] ^= self.key.k2 << 32;
+
+        self.state.permute_12();
+        self.state[3] ^= self.key.k1;
+        self.state[4] ^= self.key.k2;
+
+        let mut tag = [0u8; 16];
+        tag[..8].copy_from_slice(&self.state[3].to_be_bytes());
+        tag[8..].copy_from_slice(&self.state[4].to_be_bytes());
+        tag
+    }
+
+    pub fn encrypt(&mut self, associated_data: &[u8], plaintext: &mut [u8]) -> [u8; 16] {
+        process_associated_data(
+            &mut self.state,
+            ByteOrder::Big,
+            RATE,
+            ROUNDS,
+            associated_data,
+        );
+        process_encrypt_inplace(&mut self.state, ByteOrder::Big, RATE, ROUNDS, plaintext);
+        self.process_final()
+    }
+
+    pub fn decrypt(
+        &mut self,
+        associated_data: &[u8],
+        ciphertext: &mut [u8],
+        expected_tag: &[u8],
+    ) -> Result<(), Error> {
+        if expected_tag.len() != 16 {
+            return Err(Error::InvalidTagLength);
+        }
+
+        process_associated_data(
+            &mut self.state,
+            ByteOrder::Big,
+            RATE,
+            ROUNDS,
+            associated_data,
+        );
+        process_decrypt_inplace(&mut self.state, ByteOrder::Big, RATE, ROUNDS, ciphertext);
+        let tag = self.process_final();
+
+        lwc_core::verify_tag_or_wipe(&tag, expected_tag, ciphertext)
+    }
+}
@@ -0,0 +1,99 @@
+//! Ascon-AEAD128 as standardized in NIST SP 800-232.
+//!
+//! It has the rate and round numbers of Ascon-128a, but loads bytes into words in little-endian
+//! order, pads with a low `0x01` byte, uses a new IV and flips the top bit of the state for domain
+//! separation. Ciphertexts are not compatible with Ascon-128a.
+
+use crate::ascon_128::{
+    process_associated_data, process_decrypt_inplace, process_encrypt_inplace, Key,
+};
+use crate::ascon_core::{ByteOrder, State};
+use crate::Error;
+
+const RATE: usize = 16;
+const ROUNDS: usize = 8;
+const IV: u64 = 0x00001000808c0001;
+
+/// The key as little-endian words.
+///
+/// `Key` holds big-endian words; swapping their bytes gives the little-endian ones.
+fn key_words(key: &Key) -> (u64, u64) {
+    (key.get_k1().swap_bytes(), key.get_k2().swap_bytes())
+}
+
+pub struct AsconAead128 {
+    state: State,
+    key: Key,
+}
+
+impl AsconAead128 {
+    pub fn new(key: &Key, nonce: &[u8; 16]) -> Self {
+        let (k1, k2) = key_words(key);
+        let mut state = State::new(
+            IV,
+            k1,
+            k2,
+            ByteOrder::Little.load(&nonce[..8]),
+            ByteOrder::Little.load(&nonce[8..]),
+        );
+
+        state.permute_12();
+        state[3] ^= k1;
+        state[4] ^= k2;
+
+        Self {
+            state,
+            key: key.clone(),
+        }
+    }
+
+    fn process_final(&mut self) -> [u8; 16] {
+        let (k1, k2) = key_words(&self.key);
+        self.state[2] ^= k1;
+        self.state[3] ^= k2;
+
+        self.state.permute_12();
+        self.state[3] ^= k1;
+        self.state[4] ^= k2;
+
+        let mut tag = [0u8; 16];
+        tag[..8].copy_from_slice(&self.state[3].to_le_bytes());
+        tag[8..].copy_from_slice(&self.state[4].to_le_bytes());
+        tag
+    }
+
+    pub fn encrypt(&mut self, associated_data: &[u8], plaintext: &mut [u8]) -> [u8; 16] {
+        process_associated_data(
+            &mut self.state,
+            ByteOrder::Little,
+            RATE,
+            ROUNDS,
+            associated_data,
+        );
+        process_encrypt_inplace(&mut self.state, ByteOrder::Little, RATE, ROUNDS, plaintext);
+        self.process_final()
+    }
+
+    pub fn decrypt(
+        &mut self,
+        associated_data: &[u8],
+        ciphertext: &mut [u8],
+        expected_tag: &[u8],
+    ) -> Result<(), Error> {
+        if expected_tag.len() != 16 {
+            return Err(Error::InvalidTagLength);
+        }
+
+        process_associated_data(
+            &mut self.state,
+            ByteOrder::Little,
+            RATE,
+            ROUNDS,
+            associated_data,
+        );
+        process_decrypt_inplace(&mut self.state, ByteOrder::Little, RATE, ROUNDS, ciphertext);
+        let tag = self.process_final();
+
+        lwc_core::verify_tag_or_wipe(&tag, expected_tag, ciphertext)
+    }
+}
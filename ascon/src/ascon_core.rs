@@ -1,11 +1,87 @@
+use core::fmt;
 use core::mem::size_of;
 
+#[cfg(feature = "zeroize")]
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::Error;
+
 /// Produce mask for padding.
 #[inline(always)]
 pub const fn pad(n: usize) -> u64 {
     0x80_u64 << (56 - 8 * n)
 }
 
+/// How bytes are loaded into the words of the state.
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum ByteOrder {
+    /// Ascon v1.2: big-endian words, padded with a high `0x80` byte.
+    Big,
+    /// SP 800-232: little-endian words, padded with a low `0x01` byte.
+    Little,
+}
+
+impl ByteOrder {
+    /// Load a full word.
+    #[inline]
+    pub(crate) fn load(self, bytes: &[u8]) -> u64 {
+        let bytes = bytes.try_into().unwrap();
+        match self {
+            Self::Big => u64::from_be_bytes(bytes),
+            Self::Little => u64::from_le_bytes(bytes),
+        }
+    }
+
+    /// Load fewer than 8 bytes into the start of a word, leaving the rest zero.
+    #[inline]
+    pub(crate) fn load_partial(self, bytes: &[u8]) -> u64 {
+        let mut word = [0u8; 8];
+        word[..bytes.len()].copy_from_slice(bytes);
+        self.load(&word)
+    }
+
+    /// Load fewer than 8 bytes followed by the padding byte.
+    #[inline]
+    pub(crate) fn load_padded(self, bytes: &[u8]) -> u64 {
+        self.pad(bytes.len()) ^ self.load_partial(bytes)
+    }
+
+    #[inline]
+    pub(crate) fn store(self, word: u64) -> [u8; 8] {
+        match self {
+            Self::Big => word.to_be_bytes(),
+            Self::Little => word.to_le_bytes(),
+        }
+    }
+
+    /// Padding byte placed after the first `n` bytes of a word.
+    #[inline(always)]
+    pub(crate) const fn pad(self, n: usize) -> u64 {
+        match self {
+            Self::Big => pad(n),
+            Self::Little => 0x01_u64 << (8 * n),
+        }
+    }
+
+    /// Clear the first `n` bytes of a word, for `n` from 1 to 7.
+    #[inline(always)]
+    pub(crate) const fn clear(self, word: u64, n: usize) -> u64 {
+        match self {
+            Self::Big => word & (0x00ffffffffffffff >> (n * 8 - 8)),
+            Self::Little => word & (u64::MAX << (8 * n)),
+        }
+    }
+
+    /// Bit of the last word flipped to separate the associated data from the message.
+    #[inline(always)]
+    pub(crate) const fn domain_separation(self) -> u64 {
+        match self {
+            Self::Big => 1,
+            Self::Little => 1 << 63,
+        }
+    }
+}
+
 /// Compute round constant
 #[inline(always)]
 const fn round_constant(round: u64) -> u64 {
@@ -15,11 +91,34 @@ const fn round_constant(round: u64) -> u64 {
 /// The state of Ascon's permutation.
 ///
 /// The permutation operates on a state of 320 bits represented as 5 64 bit words.
-#[derive(Clone, Copy, Debug, Default)]
+#[derive(Clone, Default)]
 pub struct State {
     x: [u64; 5],
 }
 
+impl fmt::Debug for State {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("State").finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Zeroize for State {
+    fn zeroize(&mut self) {
+        self.x.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for State {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl ZeroizeOnDrop for State {}
+
 /// Ascon's round function
 const fn round(x: [u64; 5], c: u64) -> [u64; 5] {
     // Add round constant
@@ -90,12 +189,12 @@ impl State {
 }
 
 impl TryFrom<&[u64]> for State {
-    type Error = ();
+    type Error = Error;
 
     fn try_from(value: &[u64]) -> Result<Self, Self::Error> {
         match value.len() {
             5 => Ok(Self::new(value[0], value[1], value[2], value[3], value[4])),
-            _ => Err(()),
+            _ => Err(Error::InvalidStateLength),
         }
     }
 }
@@ -107,11 +206,11 @@ impl From<&[u64; 5]> for State {
 }
 
 impl TryFrom<&[u8]> for State {
-    type Error = ();
+    type Error = Error;
 
     fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
         if value.len() != core::mem::size_of::<u64>() * 5 {
-            return Err(());
+            return Err(Error::InvalidStateLength);
         }
 
         let mut state = Self::default();
@@ -231,7 +330,7 @@ mod tests {
             0xabcdef0123456789,
             0x89abcdef01234567,
         );
-        let mut state2 = state;
+        let mut state2 = state.clone();
         state.permute_12();
         state2.permute_n(12);
         assert_eq!(state[0], 0x206416dfc624bb14);
@@ -242,4 +341,12 @@ mod tests {
 
         assert_eq!(state, state2);
     }
+
+    #[cfg(feature = "zeroize")]
+    #[test]
+    fn zeroize_state() {
+        let mut state = State::new(1, 2, 3, 4, 5);
+        state.zeroize();
+        assert_eq!(state.as_ref(), &[0; 5]);
+    }
 }
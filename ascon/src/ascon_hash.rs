@@ -0,0 +1,216 @@
+use core::fmt;
+
+use crate::ascon_core::{ByteOrder, State};
+
+const RATE: usize = 8;
+const HASH_IV: u64 = 0x00400c0000000100;
+const HASHA_IV: u64 = 0x00400c0400000100;
+
+/// Length of the Ascon-Hash and Ascon-Hasha digests in bytes.
+pub const HASH_SIZE: usize = 32;
+
+/// Unkeyed sponge with a 64-bit rate, shared by the hash and XOF constructions.
+///
+/// `position` counts the bytes buffered in the current input block while absorbing, and the bytes
+/// already read from the current output block while squeezing.
+#[derive(Clone)]
+pub(crate) struct Sponge {
+    state: State,
+    buffer: [u8; RATE],
+    position: usize,
+    rounds: usize,
+    byte_order: ByteOrder,
+}
+
+impl Sponge {
+    /// Initialize the sponge from its IV, with `rounds` rounds of the permutation between blocks.
+    pub(crate) fn new(iv: u64, rounds: usize, byte_order: ByteOrder) -> Self {
+        let mut state = State::new(iv, 0, 0, 0, 0);
+        state.permute_12();
+        Self {
+            state,
+            buffer: [0u8; RATE],
+            position: 0,
+            rounds,
+            byte_order,
+        }
+    }
+
+    fn absorb_block(&mut self, block: &[u8]) {
+        self.state[0] ^= self.byte_order.load(block);
+        self.state.permute_n(self.rounds);
+    }
+
+    pub(crate) fn absorb(&mut self, mut data: &[u8]) {
+        if self.position > 0 {
+            let len = data.len().min(RATE - self.position);
+            self.buffer[self.position..self.position + len].copy_from_slice(&data[..len]);
+            self.position += len;
+            data = &data[len..];
+            if self.position < RATE {
+                return;
+            }
+
+            let block = self.buffer;
+            self.absorb_block(&block);
+            self.position = 0;
+        }
+
+        let mut blocks = data.chunks_exact(RATE);
+        for block in blocks.by_ref() {
+            self.absorb_block(block);
+        }
+
+        let last_block = blocks.remainder();
+        self.buffer[..last_block.len()].copy_from_slice(last_block);
+        self.position = last_block.len();
+    }
+
+    /// Pad the buffered input and permute, which ends the current input string.
+    ///
+    /// The sponge can then absorb another string or be squeezed.
+    pub(crate) fn absorb_padding(&mut self) {
+        self.state[0] ^= self.byte_order.load_padded(&self.buffer[..self.position]);
+        self.state.permute_12();
+        self.position = 0;
+    }
+
+    /// Fill `out` with the next output bytes; the permutation only runs once a block is used up.
+    pub(crate) fn squeeze(&mut self, mut out: &mut [u8]) {
+        while !out.is_empty() {
+            if self.position == RATE {
+                self.state.permute_n(self.rounds);
+                self.position = 0;
+            }
+
+            let len = out.len().min(RATE - self.position);
+            let block = self.byte_order.store(self.state[0]);
+            out[..len].copy_from_slice(&block[self.position..self.position + len]);
+            self.position += len;
+            out = &mut out[len..];
+        }
+    }
+}
+
+impl fmt::Debug for Sponge {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Sponge").finish_non_exhaustive()
+    }
+}
+
+/// Ascon-Hash, the 256-bit hash function of Ascon v1.2.
+#[derive(Clone, Debug)]
+pub struct AsconHash {
+    sponge: Sponge,
+}
+
+impl AsconHash {
+    pub fn new() -> Self {
+        Self {
+            sponge: Sponge::new(HASH_IV, 12, ByteOrder::Big),
+        }
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        self.sponge.absorb(data);
+    }
+
+    pub fn finalize(mut self) -> [u8; HASH_SIZE] {
+        let mut digest = [0u8; HASH_SIZE];
+        self.sponge.absorb_padding();
+        self.sponge.squeeze(&mut digest);
+        digest
+    }
+
+    /// Hash `data` in one call.
+    pub fn digest(data: &[u8]) -> [u8; HASH_SIZE] {
+        let mut hasher = Self::new();
+        hasher.update(data);
+        hasher.finalize()
+    }
+}
+
+impl Default for AsconHash {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Ascon-Hasha, the variant of Ascon-Hash with 8 rounds between blocks.
+#[derive(Clone, Debug)]
+pub struct AsconHasha {
+    sponge: Sponge,
+}
+
+impl AsconHasha {
+    pub fn new() -> Self {
+        Self {
+            sponge: Sponge::new(HASHA_IV, 8, ByteOrder::Big),
+        }
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        self.sponge.absorb(data);
+    }
+
+    pub fn finalize(mut self) -> [u8; HASH_SIZE] {
+        let mut digest = [0u8; HASH_SIZE];
+        self.sponge.absorb_padding();
+        self.sponge.squeeze(&mut digest);
+        digest
+    }
+
+    /// Hash `data` in one call.
+    pub fn digest(data: &[u8]) -> [u8; HASH_SIZE] {
+        let mut hasher = Self::new();
+        hasher.update(data);
+        hasher.finalize()
+    }
+}
+
+impl Default for AsconHasha {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn incremental_update() {
+        let data: [u8; 50] = core::array::from_fn(|i| i as u8);
+        let expected = AsconHash::digest(&data);
+        let expected_a = AsconHasha::digest(&data);
+        assert_ne!(expected, expected_a);
+
+        for split in 0..data.len() {
+            let mut hasher = AsconHash::new();
+            let mut hasher_a = AsconHasha::new();
+            for chunk in [&data[..split], &[], &data[split..]] {
+                hasher.update(chunk);
+                hasher_a.update(chunk);
+            }
+            assert_eq!(hasher.finalize(), expected);
+            assert_eq!(hasher_a.finalize(), expected_a);
+        }
+    }
+
+    #[test]
+    fn squeeze_in_pieces() {
+        let mut sponge = Sponge::new(HASH_IV, 12, ByteOrder::Big);
+        sponge.absorb(b"abc");
+        sponge.absorb_padding();
+        let mut pieces = sponge.clone();
+
+        let mut expected = [0u8; 40];
+        sponge.squeeze(&mut expected);
+
+        let mut out = [0u8; 40];
+        for chunk in out.chunks_mut(3) {
+            pieces.squeeze(chunk);
+        }
+        assert_eq!(out, expected);
+    }
+}
@@ -0,0 +1,277 @@
+//! Ascon-Mac, Ascon-Prf and Ascon-PrfShort, the keyed functions proposed alongside Ascon v1.2.
+//!
+//! Ascon-Mac and Ascon-Prf absorb 32 bytes per block, using every word of the state except the
+//! last one, and squeeze from the first two words. Ascon-PrfShort handles messages of up to 16
+//! bytes with a single permutation.
+
+use core::fmt;
+
+use crate::ascon_128::{u64_from_be_bytes, u64_from_be_bytes_partial, Key};
+use crate::ascon_core::State;
+use crate::Error;
+
+const RATE: usize = 32;
+const MAC_IV: u64 = 0x80808c0000000080;
+const PRF_IV: u64 = 0x80808c0000000000;
+const PRFS_IV: u64 = 0x80004c8000000000;
+
+/// Length of the tags of Ascon-Mac, Ascon-Prf and Ascon-PrfShort in bytes.
+pub const MAC_SIZE: usize = 16;
+
+/// Longest message accepted by Ascon-PrfShort, in bytes.
+pub const PRFS_MAX_MESSAGE_SIZE: usize = 16;
+
+/// Keyed sponge shared by Ascon-Mac and Ascon-Prf.
+#[derive(Clone)]
+struct KeyedSponge {
+    state: State,
+    buffer: [u8; RATE],
+    position: usize,
+}
+
+impl KeyedSponge {
+    fn new(iv: u64, key: &Key) -> Self {
+        let mut state = State::new(iv, key.get_k1(), key.get_k2(), 0, 0);
+        state.permute_12();
+        Self {
+            state,
+            buffer: [0u8; RATE],
+            position: 0,
+        }
+    }
+
+    fn absorb_block(&mut self, block: &[u8]) {
+        for (i, word) in block.chunks_exact(8).enumerate() {
+            self.state[i] ^= u64_from_be_bytes(word);
+        }
+        self.state.permute_12();
+    }
+
+    fn absorb(&mut self, mut data: &[u8]) {
+        if self.position > 0 {
+            let len = data.len().min(RATE - self.position);
+            self.buffer[self.position..self.position + len].copy_from_slice(&data[..len]);
+            self.position += len;
+            data = &data[len..];
+            if self.position < RATE {
+                return;
+            }
+
+            let block = self.buffer;
+            self.absorb_block(&block);
+            self.position = 0;
+        }
+
+        let mut blocks = data.chunks_exact(RATE);
+        for block in blocks.by_ref() {
+            self.absorb_block(block);
+        }
+
+        let last_block = blocks.remainder();
+        self.buffer[..last_block.len()].copy_from_slice(last_block);
+        self.position = last_block.len();
+    }
+
+    fn finalize(mut self) -> [u8; MAC_SIZE] {
+        // The last block is always padded, with a `0x80` byte followed by zeros, so a message
+        // filling its last block gets an extra block made of padding only.
+        self.buffer[self.position] = 0x80;
+        self.buffer[self.position + 1..].fill(0);
+        for (i, word) in self.buffer.chunks_exact(8).enumerate() {
+            self.state[i] ^= u64_from_be_bytes(word);
+        }
+        self.state[4] ^= 1;
+        self.state.permute_12();
+
+        let mut tag = [0u8; MAC_SIZE];
+        tag[..8].copy_from_slice(&self.state[0].to_be_bytes());
+        tag[8..].copy_from_slice(&self.state[1].to_be_bytes());
+        tag
+    }
+}
+
+impl fmt::Debug for KeyedSponge {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KeyedSponge").finish_non_exhaustive()
+    }
+}
+
+/// Ascon-Mac, a message authentication code with 128-bit tags.
+#[derive(Clone, Debug)]
+pub struct AsconMac {
+    sponge: KeyedSponge,
+}
+
+impl AsconMac {
+    pub fn new(key: &Key) -> Self {
+        Self {
+            sponge: KeyedSponge::new(MAC_IV, key),
+        }
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        self.sponge.absorb(data);
+    }
+
+    pub fn finalize(self) -> [u8; MAC_SIZE] {
+        self.sponge.finalize()
+    }
+
+    /// Check `tag` against the tag of the data absorbed so far, in constant time.
+    pub fn verify(self, tag: &[u8]) -> Result<(), Error> {
+        lwc_core::verify_tag(&self.finalize(), tag)
+    }
+}
+
+/// Ascon-Prf, a pseudorandom function, here truncated to 128 bits of output.
+#[derive(Clone, Debug)]
+pub struct AsconPrf {
+    sponge: KeyedSponge,
+}
+
+impl AsconPrf {
+    pub fn new(key: &Key) -> Self {
+        Self {
+            sponge: KeyedSponge::new(PRF_IV, key),
+        }
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        self.sponge.absorb(data);
+    }
+
+    pub fn finalize(self) -> [u8; MAC_SIZE] {
+        self.sponge.finalize()
+    }
+
+    /// Check `tag` against the output for the data absorbed so far, in constant time.
+    pub fn verify(self, tag: &[u8]) -> Result<(), Error> {
+        lwc_core::verify_tag(&self.finalize(), tag)
+    }
+}
+
+/// Ascon-PrfShort, a pseudorandom function for messages of at most
+/// [`PRFS_MAX_MESSAGE_SIZE`] bytes that needs a single permutation.
+#[derive(Clone, Debug)]
+pub struct AsconPrfShort {
+    key: Key,
+}
+
+impl AsconPrfShort {
+    pub fn new(key: &Key) -> Self {
+        Self { key: key.clone() }
+    }
+
+    /// Compute the tag of `message`, rejecting messages longer than [`PRFS_MAX_MESSAGE_SIZE`].
+    pub fn compute(&self, message: &[u8]) -> Result<[u8; MAC_SIZE], Error> {
+        if message.len() > PRFS_MAX_MESSAGE_SIZE {
+            return Err(Error::InvalidMessageLength);
+        }
+
+        // The message length in bits goes into the second byte of the IV.
+        let iv = PRFS_IV | ((8 * message.len() as u64) << 48);
+        let (m0, m1) = message.split_at(message.len().min(8));
+        let mut state = State::new(
+            iv,
+            self.key.get_k1(),
+            self.key.get_k2(),
+            u64_from_be_bytes_partial(m0),
+            u64_from_be_bytes_partial(m1),
+        );
+        state.permute_12();
+
+        let mut tag = [0u8; MAC_SIZE];
+        tag[..8].copy_from_slice(&(state[3] ^ self.key.get_k1()).to_be_bytes());
+        tag[8..].copy_from_slice(&(state[4] ^ self.key.get_k2()).to_be_bytes());
+        Ok(tag)
+    }
+
+    /// Check `tag` against the tag of `message`, in constant time.
+    pub fn verify(&self, message: &[u8], tag: &[u8]) -> Result<(), Error> {
+        lwc_core::verify_tag(&self.compute(message)?, tag)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: [u8; 16] = [
+        0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e,
+        0x0f,
+    ];
+
+    #[test]
+    fn incremental_update() {
+        let key = Key::from(&KEY);
+        let data: [u8; 100] = core::array::from_fn(|i| i as u8);
+        let mut mac = AsconMac::new(&key);
+        let mut prf = AsconPrf::new(&key);
+        mac.update(&data);
+        prf.update(&data);
+        let expected = mac.finalize();
+        let expected_prf = prf.finalize();
+        assert_ne!(expected, expected_prf);
+
+        for split in 0..data.len() {
+            let mut mac = AsconMac::new(&key);
+            let mut prf = AsconPrf::new(&key);
+            for chunk in [&data[..split], &[], &data[split..]] {
+                mac.update(chunk);
+                prf.update(chunk);
+            }
+            assert_eq!(mac.finalize(), expected);
+            assert_eq!(prf.finalize(), expected_prf);
+        }
+    }
+
+    #[test]
+    fn padding_block() {
+        // A short message and the same message padded by hand to a full block must not collide.
+        let key = Key::from(&KEY);
+        let mut short = AsconMac::new(&key);
+        short.update(&[0u8; RATE - 1]);
+        let mut padded = AsconMac::new(&key);
+        padded.update(&[0u8; RATE - 1]);
+        padded.update(&[0x80]);
+        assert_ne!(short.finalize(), padded.finalize());
+    }
+
+    #[test]
+    fn verify() {
+        let key = Key::from(&KEY);
+        let mut mac = AsconMac::new(&key);
+        mac.update(b"message");
+        let mut tag = mac.clone().finalize();
+        assert_eq!(mac.clone().verify(&tag), Ok(()));
+        assert_eq!(mac.clone().verify(&tag[..8]), Err(Error::InvalidTagLength));
+        tag[15] ^= 1;
+        assert_eq!(mac.verify(&tag), Err(Error::AuthenticationFailed));
+
+        let mut other = AsconMac::new(&Key::from(&[0u8; 16]));
+        other.update(b"message");
+        assert_eq!(other.verify(&tag), Err(Error::AuthenticationFailed));
+    }
+
+    #[test]
+    fn prf_short() {
+        let prfs = AsconPrfShort::new(&Key::from(&KEY));
+        let mut tags = [[0u8; MAC_SIZE]; PRFS_MAX_MESSAGE_SIZE + 1];
+        for (len, tag) in tags.iter_mut().enumerate() {
+            *tag = prfs.compute(&[0u8; PRFS_MAX_MESSAGE_SIZE][..len]).unwrap();
+            assert_eq!(
+                prfs.verify(&[0u8; PRFS_MAX_MESSAGE_SIZE][..len], tag),
+                Ok(())
+            );
+        }
+        // The message length is part of the IV, so zero bytes of different lengths all differ.
+        for (i, tag) in tags.iter().enumerate() {
+            assert!(tags[i + 1..].iter().all(|other| other != tag));
+        }
+
+        assert_eq!(
+            prfs.compute(&[0u8; PRFS_MAX_MESSAGE_SIZE + 1]),
+            Err(Error::InvalidMessageLength)
+        );
+    }
+}
@@ -0,0 +1,195 @@
+use crate::ascon_core::ByteOrder;
+use crate::ascon_hash::Sponge;
+use crate::Error;
+
+const XOF_IV: u64 = 0x00400c0000000000;
+const XOFA_IV: u64 = 0x00400c0400000000;
+const XOF128_IV: u64 = 0x0000080000cc0003;
+const CXOF128_IV: u64 = 0x0000080000cc0004;
+
+/// Longest customization string accepted by Ascon-CXOF128, in bytes.
+pub const CUSTOMIZATION_MAX_SIZE: usize = 256;
+
+/// Output of an extendable-output function, read with as many calls to [`squeeze`] as needed.
+///
+/// [`squeeze`]: AsconXofReader::squeeze
+#[derive(Clone, Debug)]
+pub struct AsconXofReader {
+    sponge: Sponge,
+}
+
+impl AsconXofReader {
+    fn new(mut sponge: Sponge) -> Self {
+        sponge.absorb_padding();
+        Self { sponge }
+    }
+
+    /// Fill `out` with the next bytes of output.
+    pub fn squeeze(&mut self, out: &mut [u8]) {
+        self.sponge.squeeze(out);
+    }
+}
+
+/// Ascon-Xof, the extendable-output function of Ascon v1.2.
+#[derive(Clone, Debug)]
+pub struct AsconXof {
+    sponge: Sponge,
+}
+
+impl AsconXof {
+    pub fn new() -> Self {
+        Self {
+            sponge: Sponge::new(XOF_IV, 12, ByteOrder::Big),
+        }
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        self.sponge.absorb(data);
+    }
+
+    pub fn finalize_xof(self) -> AsconXofReader {
+        AsconXofReader::new(self.sponge)
+    }
+}
+
+impl Default for AsconXof {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Ascon-Xofa, the variant of Ascon-Xof with 8 rounds between blocks.
+#[derive(Clone, Debug)]
+pub struct AsconXofa {
+    sponge: Sponge,
+}
+
+impl AsconXofa {
+    pub fn new() -> Self {
+        Self {
+            sponge: Sponge::new(XOFA_IV, 8, ByteOrder::Big),
+        }
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        self.sponge.absorb(data);
+    }
+
+    pub fn finalize_xof(self) -> AsconXofReader {
+        AsconXofReader::new(self.sponge)
+    }
+}
+
+impl Default for AsconXofa {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Ascon-XOF128 as standardized in NIST SP 800-232.
+#[derive(Clone, Debug)]
+pub struct AsconXof128 {
+    sponge: Sponge,
+}
+
+impl AsconXof128 {
+    pub fn new() -> Self {
+        Self {
+            sponge: Sponge::new(XOF128_IV, 12, ByteOrder::Little),
+        }
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        self.sponge.absorb(data);
+    }
+
+    pub fn finalize_xof(self) -> AsconXofReader {
+        AsconXofReader::new(self.sponge)
+    }
+}
+
+impl Default for AsconXof128 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Ascon-CXOF128 as standardized in NIST SP 800-232: Ascon-XOF128 with a customization string
+/// that separates the outputs of different applications.
+#[derive(Clone, Debug)]
+pub struct AsconCxof128 {
+    sponge: Sponge,
+}
+
+impl AsconCxof128 {
+    /// Create a new instance, rejecting customization strings longer than
+    /// [`CUSTOMIZATION_MAX_SIZE`].
+    pub fn new(customization: &[u8]) -> Result<Self, Error> {
+        if customization.len() > CUSTOMIZATION_MAX_SIZE {
+            return Err(Error::InvalidCustomizationLength);
+        }
+
+        let mut sponge = Sponge::new(CXOF128_IV, 12, ByteOrder::Little);
+        // The customization string is absorbed first, preceded by its length in bits.
+        sponge.absorb(&(8 * customization.len() as u64).to_le_bytes());
+        sponge.absorb(customization);
+        sponge.absorb_padding();
+        Ok(Self { sponge })
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        self.sponge.absorb(data);
+    }
+
+    pub fn finalize_xof(self) -> AsconXofReader {
+        AsconXofReader::new(self.sponge)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn squeeze_repeatedly() {
+        let mut xof = AsconXof128::new();
+        xof.update(b"input");
+
+        let mut expected = [0u8; 100];
+        xof.clone().finalize_xof().squeeze(&mut expected);
+
+        let mut reader = xof.finalize_xof();
+        let mut out = [0u8; 100];
+        let (first, rest) = out.split_at_mut(7);
+        reader.squeeze(first);
+        reader.squeeze(&mut []);
+        for chunk in rest.chunks_mut(9) {
+            reader.squeeze(chunk);
+        }
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    fn customization() {
+        let mut plain = [0u8; 32];
+        AsconXof128::new().finalize_xof().squeeze(&mut plain);
+        let mut empty = [0u8; 32];
+        AsconCxof128::new(&[])
+            .unwrap()
+            .finalize_xof()
+            .squeeze(&mut empty);
+        let mut custom = [0u8; 32];
+        AsconCxof128::new(b"app")
+            .unwrap()
+            .finalize_xof()
+            .squeeze(&mut custom);
+        assert_ne!(plain, empty);
+        assert_ne!(empty, custom);
+
+        assert!(AsconCxof128::new(&[0u8; CUSTOMIZATION_MAX_SIZE]).is_ok());
+        assert_eq!(
+            AsconCxof128::new(&[0u8; CUSTOMIZATION_MAX_SIZE + 1]).err(),
+            Some(Error::InvalidCustomizationLength)
+        );
+    }
+}
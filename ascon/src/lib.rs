@@ -3,40 +3,169 @@
 
 pub use alloc::vec::Vec;
 
+#[cfg(feature = "aead")]
+pub use aead;
 use ascon_128::Ascon128;
 pub use ascon_128::Key;
+use ascon_128a::Ascon128a;
+use ascon_80pq::Ascon80pq;
+pub use ascon_80pq::Key80pq;
+use ascon_aead128::AsconAead128;
+pub use ascon_hash::{AsconHash, AsconHasha, HASH_SIZE};
+pub use ascon_mac::{AsconMac, AsconPrf, AsconPrfShort, MAC_SIZE, PRFS_MAX_MESSAGE_SIZE};
+pub use ascon_xof::{
+    AsconCxof128, AsconXof, AsconXof128, AsconXofReader, AsconXofa, CUSTOMIZATION_MAX_SIZE,
+};
+#[cfg(feature = "digest")]
+pub use digest;
+use lwc_core::Aead;
+pub use lwc_core::Error;
+#[cfg(feature = "std")]
+pub use lwc_core::{DecryptReader, EncryptWriter};
+pub use stream::{AsconDecryptor, AsconEncryptor};
 
 extern crate alloc;
 
 mod ascon_128;
+mod ascon_128a;
+mod ascon_80pq;
+mod ascon_aead128;
 mod ascon_core;
+mod ascon_hash;
+mod ascon_mac;
+mod ascon_xof;
+#[cfg(feature = "aead")]
+mod rustcrypto;
+#[cfg(feature = "digest")]
+mod rustcrypto_digest;
+mod stream;
 
-pub struct AsconHead {
-    key: Key,
+/// Define the public type of one Ascon AEAD variant: its constructors, its one-shot encryption and
+/// decryption, and its [`Aead`] impl, all of which only differ in the internal cipher and the key.
+macro_rules! ascon_head {
+    ($(#[$attr:meta])* $name:ident, $internal:ident, $key:ident, $key_size:literal) => {
+        $(#[$attr])*
+        #[derive(Debug)]
+        pub struct $name {
+            key: $key,
+        }
+
+        impl $name {
+            pub fn new(key: $key) -> Self {
+                Self { key }
+            }
+
+            pub fn new_from_slice(key: &[u8]) -> Result<Self, Error> {
+                Ok(Self::new($key::try_from(key)?))
+            }
+
+            pub fn encrypt(
+                &self,
+                nonce: &[u8; 16],
+                associated_data: &[u8],
+                plaintext: &mut [u8],
+            ) -> Vec<u8> {
+                let mut internal = $internal::new(&self.key, nonce);
+                internal.encrypt(associated_data, plaintext).to_vec()
+            }
+
+            pub fn decrypt(
+                &self,
+                nonce: &[u8; 16],
+                associated_data: &[u8],
+                ciphertext: &mut [u8],
+                expected_tag: &[u8],
+            ) -> Result<(), Error> {
+                let mut internal = $internal::new(&self.key, nonce);
+                internal.decrypt(associated_data, ciphertext, expected_tag)
+            }
+        }
+
+        impl TryFrom<&[u8]> for $name {
+            type Error = Error;
+
+            fn try_from(key: &[u8]) -> Result<Self, Self::Error> {
+                Self::new_from_slice(key)
+            }
+        }
+
+        impl Aead for $name {
+            const KEY_SIZE: usize = $key_size;
+            const NONCE_SIZE: usize = 16;
+            const TAG_SIZE: usize = 16;
+
+            type Tag = [u8; 16];
+
+            fn new_from_slice(key: &[u8]) -> Result<Self, Error> {
+                Self::new_from_slice(key)
+            }
+
+            fn encrypt_inplace_detached(
+                &self,
+                nonce: &[u8],
+                associated_data: &[u8],
+                buffer: &mut [u8],
+            ) -> Result<Self::Tag, Error> {
+                let nonce = nonce.try_into().map_err(|_| Error::InvalidNonceLength)?;
+                let mut internal = $internal::new(&self.key, nonce);
+                Ok(internal.encrypt(associated_data, buffer))
+            }
+
+            fn decrypt_inplace_detached(
+                &self,
+                nonce: &[u8],
+                associated_data: &[u8],
+                buffer: &mut [u8],
+                tag: &[u8],
+            ) -> Result<(), Error> {
+                let nonce = nonce.try_into().map_err(|_| Error::InvalidNonceLength)?;
+                self.decrypt(nonce, associated_data, buffer, tag)
+            }
+        }
+    };
 }
 
-impl AsconHead {
-    pub fn new(key: Key) -> Self {
-        Self { key }
-    }
+ascon_head!(AsconHead, Ascon128, Key, 16);
 
-    pub fn encrypt(&self, nonce: &[u8], associated_data: &[u8], plaintext: &mut [u8]) -> Vec<u8> {
-        let mut internal = Ascon128::new(self.key, nonce);
-        internal.encrypt(associated_data, plaintext)
+impl AsconHead {
+    /// Start encrypting a message given in fragments.
+    pub fn encryptor(&self, nonce: &[u8; 16]) -> AsconEncryptor {
+        AsconEncryptor::new(&self.key, nonce)
     }
 
-    pub fn decrypt(
-        &self,
-        nonce: &[u8],
-        associated_data: &[u8],
-        ciphertext: &mut [u8],
-        expected_tag: &[u8],
-    ) -> Result<(), ()> {
-        let mut internal = Ascon128::new(self.key, nonce);
-        internal.decrypt(associated_data, ciphertext, expected_tag)
+    /// Start decrypting a message given in fragments.
+    pub fn decryptor(&self, nonce: &[u8; 16]) -> AsconDecryptor {
+        AsconDecryptor::new(&self.key, nonce)
     }
 }
 
+ascon_head!(
+    /// Ascon-128a, the variant with a 128-bit rate, for bulk data.
+    Ascon128aHead,
+    Ascon128a,
+    Key,
+    16
+);
+
+ascon_head!(
+    /// Ascon-80pq, the variant with a 160-bit key for extra margin against quantum key search.
+    Ascon80pqHead,
+    Ascon80pq,
+    Key80pq,
+    20
+);
+
+ascon_head!(
+    /// Ascon-AEAD128 as standardized in NIST SP 800-232.
+    ///
+    /// This is not compatible with [`AsconHead`] or [`Ascon128aHead`], which implement the Ascon
+    /// v1.2 submission and remain available to decrypt existing data.
+    AsconAead128Head,
+    AsconAead128,
+    Key,
+    16
+);
+
 #[cfg(test)]
 mod tests {
 
@@ -57,25 +186,182 @@ mod tests {
 
     #[test]
     fn ascon128() {
-        let key: Key = Key::from(KEY.as_ref());
+        let key: Key = Key::from(&KEY);
         let cipher = AsconHead::new(key);
 
         let mut plaintext = TEST_PLAIN_TEXT.as_bytes().to_vec();
-        let tag = cipher.encrypt(NONCE.as_ref(), &[], &mut plaintext);
+        let tag = cipher.encrypt(&NONCE, &[], &mut plaintext);
         let mut ciphertext = plaintext;
-        assert!(cipher
-            .decrypt(NONCE.as_ref(), &[], &mut ciphertext, &tag)
-            .is_ok());
+        assert!(cipher.decrypt(&NONCE, &[], &mut ciphertext, &tag).is_ok());
 
         assert_eq!(TEST_PLAIN_TEXT.as_bytes(), &ciphertext);
 
         let mut plaintext = TEST_LONG_PLAIN_TEXT.as_bytes().to_vec();
-        let tag = cipher.encrypt(NONCE.as_ref(), &[], &mut plaintext);
+        let tag = cipher.encrypt(&NONCE, &[], &mut plaintext);
         let mut ciphertext = plaintext;
+        assert!(cipher.decrypt(&NONCE, &[], &mut ciphertext, &tag).is_ok());
+
+        assert_eq!(TEST_LONG_PLAIN_TEXT.as_bytes(), &ciphertext);
+    }
+
+    #[test]
+    fn ascon128_aead() {
+        let cipher = AsconHead::new(Key::from(&KEY));
+
+        let mut buffer = TEST_LONG_PLAIN_TEXT.as_bytes().to_vec();
+        cipher
+            .encrypt_inplace(&NONCE, b"header", &mut buffer)
+            .unwrap();
+        assert_eq!(
+            buffer.len(),
+            TEST_LONG_PLAIN_TEXT.len() + AsconHead::TAG_SIZE
+        );
+
         assert!(cipher
-            .decrypt(NONCE.as_ref(), &[], &mut ciphertext, &tag)
+            .decrypt_inplace(&NONCE, b"header", &mut buffer)
             .is_ok());
+        assert_eq!(TEST_LONG_PLAIN_TEXT.as_bytes(), &buffer);
+    }
 
-        assert_eq!(TEST_LONG_PLAIN_TEXT.as_bytes(), &ciphertext);
+    #[test]
+    fn ascon128_forged_tag() {
+        let cipher = AsconHead::new(Key::from(&KEY));
+
+        let mut buffer = TEST_PLAIN_TEXT.as_bytes().to_vec();
+        let mut tag = cipher.encrypt(&NONCE, &[], &mut buffer);
+        let ciphertext = buffer.clone();
+        assert_eq!(
+            cipher.decrypt(&NONCE, &[], &mut buffer, &tag[..15]),
+            Err(Error::InvalidTagLength)
+        );
+        assert_eq!(buffer, ciphertext);
+
+        tag[0] ^= 1;
+        assert_eq!(
+            cipher.decrypt(&NONCE, &[], &mut buffer, &tag),
+            Err(Error::AuthenticationFailed)
+        );
+        assert!(buffer.iter().all(|&byte| byte == 0));
+    }
+
+    #[test]
+    fn ascon128_lengths() {
+        assert_eq!(
+            Key::try_from(&KEY[..8]).err().unwrap(),
+            Error::InvalidKeyLength
+        );
+        assert_eq!(
+            AsconHead::new_from_slice(&[0u8; 17]).err(),
+            Some(Error::InvalidKeyLength)
+        );
+
+        let cipher = AsconHead::try_from(KEY.as_ref()).unwrap();
+        let mut buffer = [0u8; 16];
+        assert_eq!(
+            cipher.encrypt_inplace_detached(&NONCE[..8], &[], &mut buffer),
+            Err(Error::InvalidNonceLength)
+        );
+        assert_eq!(
+            cipher.decrypt_inplace_detached(&[0u8; 17], &[], &mut buffer, &[0u8; 16]),
+            Err(Error::InvalidNonceLength)
+        );
+    }
+
+    #[test]
+    fn ascon128_debug_is_redacted() {
+        let cipher = AsconHead::new(Key::from(&[0xab; 16]));
+        let debug = alloc::format!("{cipher:?}");
+        assert_eq!(debug, "AsconHead { key: Key { .. } }");
+    }
+
+    #[test]
+    fn ascon128a() {
+        let cipher = Ascon128aHead::new(Key::from(&KEY));
+
+        for len in [0, 7, 8, 15, 16, 17, TEST_LONG_PLAIN_TEXT.len()] {
+            let mut buffer = TEST_LONG_PLAIN_TEXT.as_bytes()[..len].to_vec();
+            let tag = cipher.encrypt(&NONCE, b"header", &mut buffer);
+            assert!(cipher.decrypt(&NONCE, b"header", &mut buffer, &tag).is_ok());
+            assert_eq!(&TEST_LONG_PLAIN_TEXT.as_bytes()[..len], &buffer);
+        }
+
+        let mut ascon128 = TEST_PLAIN_TEXT.as_bytes().to_vec();
+        let mut ascon128a = ascon128.clone();
+        AsconHead::new(Key::from(&KEY)).encrypt(&NONCE, &[], &mut ascon128);
+        cipher.encrypt(&NONCE, &[], &mut ascon128a);
+        assert_ne!(ascon128, ascon128a);
+    }
+
+    #[test]
+    fn ascon128a_forged_tag() {
+        let cipher = Ascon128aHead::new(Key::from(&KEY));
+
+        let mut buffer = TEST_LONG_PLAIN_TEXT.as_bytes().to_vec();
+        let mut tag = cipher.encrypt(&NONCE, &[], &mut buffer);
+        tag[15] ^= 1;
+        assert_eq!(
+            cipher.decrypt(&NONCE, &[], &mut buffer, &tag),
+            Err(Error::AuthenticationFailed)
+        );
+        assert!(buffer.iter().all(|&byte| byte == 0));
+    }
+
+    #[test]
+    fn ascon80pq() {
+        let key: [u8; 20] = core::array::from_fn(|i| i as u8);
+        let cipher = Ascon80pqHead::new(Key80pq::from(&key));
+
+        let mut buffer = TEST_LONG_PLAIN_TEXT.as_bytes().to_vec();
+        let mut tag = cipher.encrypt(&NONCE, b"header", &mut buffer);
+        assert!(cipher.decrypt(&NONCE, b"header", &mut buffer, &tag).is_ok());
+        assert_eq!(TEST_LONG_PLAIN_TEXT.as_bytes(), &buffer);
+
+        cipher.encrypt(&NONCE, b"header", &mut buffer);
+        tag[0] ^= 1;
+        assert_eq!(
+            cipher.decrypt(&NONCE, b"header", &mut buffer, &tag),
+            Err(Error::AuthenticationFailed)
+        );
+        assert!(buffer.iter().all(|&byte| byte == 0));
+    }
+
+    #[test]
+    fn ascon80pq_lengths() {
+        assert_eq!(
+            Key80pq::try_from(KEY.as_ref()).err(),
+            Some(Error::InvalidKeyLength)
+        );
+        assert!(Ascon80pqHead::new_from_slice(&[0u8; 20]).is_ok());
+        assert_eq!(
+            alloc::format!("{:?}", Key80pq::from(&[0xab; 20])),
+            "Key80pq { .. }"
+        );
+    }
+
+    #[test]
+    fn ascon_aead128() {
+        let cipher = AsconAead128Head::new(Key::from(&KEY));
+
+        for len in [0, 7, 8, 15, 16, 17, TEST_LONG_PLAIN_TEXT.len()] {
+            let mut buffer = TEST_LONG_PLAIN_TEXT.as_bytes()[..len].to_vec();
+            let mut tag = cipher.encrypt(&NONCE, b"header", &mut buffer);
+            let ciphertext = buffer.clone();
+            assert!(cipher.decrypt(&NONCE, b"header", &mut buffer, &tag).is_ok());
+            assert_eq!(&TEST_LONG_PLAIN_TEXT.as_bytes()[..len], &buffer);
+
+            buffer = ciphertext;
+            tag[8] ^= 1;
+            assert_eq!(
+                cipher.decrypt(&NONCE, b"header", &mut buffer, &tag),
+                Err(Error::AuthenticationFailed)
+            );
+            assert!(buffer.iter().all(|&byte| byte == 0));
+        }
+
+        let mut ascon128a = TEST_PLAIN_TEXT.as_bytes().to_vec();
+        let mut ascon_aead128 = ascon128a.clone();
+        Ascon128aHead::new(Key::from(&KEY)).encrypt(&NONCE, &[], &mut ascon128a);
+        cipher.encrypt(&NONCE, &[], &mut ascon_aead128);
+        assert_ne!(ascon128a, ascon_aead128);
     }
 }
@@ -0,0 +1,155 @@
+use aead::consts::{U0, U16, U20};
+use aead::{AeadCore, AeadInPlace, Key, KeyInit, KeySizeUser, Nonce, Tag};
+
+use crate::ascon_128::Ascon128;
+use crate::ascon_128a::Ascon128a;
+use crate::ascon_80pq::Ascon80pq;
+use crate::ascon_aead128::AsconAead128;
+use crate::{Ascon128aHead, Ascon80pqHead, AsconAead128Head, AsconHead, Key as AsconKey, Key80pq};
+
+/// Implement the RustCrypto traits for one Ascon AEAD variant on top of its internal cipher.
+macro_rules! rustcrypto_head {
+    ($name:ident, $internal:ident, $key:ident, $key_size:ident) => {
+        impl KeySizeUser for $name {
+            type KeySize = $key_size;
+        }
+
+        impl KeyInit for $name {
+            fn new(key: &Key<Self>) -> Self {
+                $name::new($key::from(key.as_ref()))
+            }
+        }
+
+        impl AeadCore for $name {
+            type NonceSize = U16;
+            type TagSize = U16;
+            type CiphertextOverhead = U0;
+        }
+
+        impl AeadInPlace for $name {
+            fn encrypt_in_place_detached(
+                &self,
+                nonce: &Nonce<Self>,
+                associated_data: &[u8],
+                buffer: &mut [u8],
+            ) -> aead::Result<Tag<Self>> {
+                Ok($internal::new(&self.key, nonce.as_ref())
+                    .encrypt(associated_data, buffer)
+                    .into())
+            }
+
+            fn decrypt_in_place_detached(
+                &self,
+                nonce: &Nonce<Self>,
+                associated_data: &[u8],
+                buffer: &mut [u8],
+                tag: &Tag<Self>,
+            ) -> aead::Result<()> {
+                self.decrypt(nonce.as_ref(), associated_data, buffer, tag)
+                    .map_err(|_| aead::Error)
+            }
+        }
+    };
+}
+
+rustcrypto_head!(AsconHead, Ascon128, AsconKey, U16);
+rustcrypto_head!(Ascon128aHead, Ascon128a, AsconKey, U16);
+rustcrypto_head!(Ascon80pqHead, Ascon80pq, Key80pq, U20);
+rustcrypto_head!(AsconAead128Head, AsconAead128, AsconKey, U16);
+
+#[cfg(test)]
+mod tests {
+    use aead::{Aead, Payload};
+
+    use super::*;
+
+    const KEY: [u8; 16] = [
+        0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e,
+        0x0f,
+    ];
+
+    const NONCE: [u8; 16] = [
+        0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18, 0x19, 0x1a, 0x1b, 0x1c, 0x1d, 0x1e,
+        0x1f,
+    ];
+
+    #[test]
+    fn rustcrypto_aead() {
+        let cipher = <AsconHead as KeyInit>::new(&KEY.into());
+        let nonce = Nonce::<AsconHead>::from(NONCE);
+        let payload = Payload {
+            msg: b"Hello, world!",
+            aad: b"header",
+        };
+
+        let ciphertext = Aead::encrypt(&cipher, &nonce, payload).unwrap();
+
+        let mut expected = b"Hello, world!".to_vec();
+        let tag = AsconHead::new(AsconKey::from(&KEY)).encrypt(&NONCE, b"header", &mut expected);
+        expected.extend_from_slice(&tag);
+        assert_eq!(ciphertext, expected);
+
+        let payload = Payload {
+            msg: &ciphertext,
+            aad: b"header",
+        };
+        let plaintext = Aead::decrypt(&cipher, &nonce, payload).unwrap();
+        assert_eq!(plaintext, b"Hello, world!");
+    }
+
+    /// Run a round trip through the RustCrypto traits, compare it with the native [`Aead`] output
+    /// and check that a forged tag or different associated data is rejected.
+    ///
+    /// [`Aead`]: lwc_core::Aead
+    fn round_trip<C: KeyInit + AeadInPlace + lwc_core::Aead>(key: &[u8]) {
+        let cipher = <C as KeyInit>::new_from_slice(key).unwrap();
+        let nonce = Nonce::<C>::from_slice(&NONCE);
+        let payload = Payload {
+            msg: b"Hello, world!",
+            aad: b"header",
+        };
+
+        let ciphertext = Aead::encrypt(&cipher, nonce, payload).unwrap();
+
+        let mut expected = b"Hello, world!".to_vec();
+        lwc_core::Aead::encrypt_inplace(&cipher, &NONCE, b"header", &mut expected).unwrap();
+        assert_eq!(ciphertext, expected);
+
+        let payload = Payload {
+            msg: &ciphertext,
+            aad: b"header",
+        };
+        let plaintext = Aead::decrypt(&cipher, nonce, payload).unwrap();
+        assert_eq!(plaintext, b"Hello, world!");
+
+        let mut forged = ciphertext.clone();
+        let last = forged.len() - 1;
+        forged[last] ^= 1;
+        let payload = Payload {
+            msg: &forged,
+            aad: b"header",
+        };
+        assert!(Aead::decrypt(&cipher, nonce, payload).is_err());
+
+        let payload = Payload {
+            msg: &ciphertext,
+            aad: b"footer",
+        };
+        assert!(Aead::decrypt(&cipher, nonce, payload).is_err());
+    }
+
+    #[test]
+    fn rustcrypto_aead_128a() {
+        round_trip::<Ascon128aHead>(&KEY);
+    }
+
+    #[test]
+    fn rustcrypto_aead_80pq() {
+        round_trip::<Ascon80pqHead>(&[0x42; 20]);
+    }
+
+    #[test]
+    fn rustcrypto_aead_aead128() {
+        round_trip::<AsconAead128Head>(&KEY);
+    }
+}
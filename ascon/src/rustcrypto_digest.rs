@@ -0,0 +1,177 @@
+use digest::consts::U32;
+use digest::{
+    ExtendableOutput, FixedOutput, FixedOutputReset, HashMarker, Output, OutputSizeUser, Reset,
+    Update, XofReader,
+};
+
+use crate::{
+    AsconCxof128, AsconHash, AsconHasha, AsconXof, AsconXof128, AsconXofReader, AsconXofa,
+};
+
+impl HashMarker for AsconHash {}
+
+impl OutputSizeUser for AsconHash {
+    type OutputSize = U32;
+}
+
+impl Update for AsconHash {
+    fn update(&mut self, data: &[u8]) {
+        AsconHash::update(self, data);
+    }
+}
+
+impl FixedOutput for AsconHash {
+    fn finalize_into(self, out: &mut Output<Self>) {
+        out.copy_from_slice(&AsconHash::finalize(self));
+    }
+}
+
+impl Reset for AsconHash {
+    fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
+
+impl FixedOutputReset for AsconHash {
+    fn finalize_into_reset(&mut self, out: &mut Output<Self>) {
+        out.copy_from_slice(&core::mem::take(self).finalize());
+    }
+}
+
+impl HashMarker for AsconHasha {}
+
+impl OutputSizeUser for AsconHasha {
+    type OutputSize = U32;
+}
+
+impl Update for AsconHasha {
+    fn update(&mut self, data: &[u8]) {
+        AsconHasha::update(self, data);
+    }
+}
+
+impl FixedOutput for AsconHasha {
+    fn finalize_into(self, out: &mut Output<Self>) {
+        out.copy_from_slice(&AsconHasha::finalize(self));
+    }
+}
+
+impl Reset for AsconHasha {
+    fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
+
+impl FixedOutputReset for AsconHasha {
+    fn finalize_into_reset(&mut self, out: &mut Output<Self>) {
+        out.copy_from_slice(&core::mem::take(self).finalize());
+    }
+}
+
+impl Update for AsconXof {
+    fn update(&mut self, data: &[u8]) {
+        AsconXof::update(self, data);
+    }
+}
+
+impl ExtendableOutput for AsconXof {
+    type Reader = AsconXofReader;
+
+    fn finalize_xof(self) -> Self::Reader {
+        AsconXof::finalize_xof(self)
+    }
+}
+
+impl Update for AsconXofa {
+    fn update(&mut self, data: &[u8]) {
+        AsconXofa::update(self, data);
+    }
+}
+
+impl ExtendableOutput for AsconXofa {
+    type Reader = AsconXofReader;
+
+    fn finalize_xof(self) -> Self::Reader {
+        AsconXofa::finalize_xof(self)
+    }
+}
+
+impl Update for AsconXof128 {
+    fn update(&mut self, data: &[u8]) {
+        AsconXof128::update(self, data);
+    }
+}
+
+impl ExtendableOutput for AsconXof128 {
+    type Reader = AsconXofReader;
+
+    fn finalize_xof(self) -> Self::Reader {
+        AsconXof128::finalize_xof(self)
+    }
+}
+
+impl Update for AsconCxof128 {
+    fn update(&mut self, data: &[u8]) {
+        AsconCxof128::update(self, data);
+    }
+}
+
+impl ExtendableOutput for AsconCxof128 {
+    type Reader = AsconXofReader;
+
+    fn finalize_xof(self) -> Self::Reader {
+        AsconCxof128::finalize_xof(self)
+    }
+}
+
+impl XofReader for AsconXofReader {
+    fn read(&mut self, buffer: &mut [u8]) {
+        self.squeeze(buffer);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use digest::Digest;
+
+    use super::*;
+
+    fn digest_matches<D: Digest + FixedOutputReset>(expected: [u8; 32]) {
+        let mut hasher = D::new();
+        Digest::update(&mut hasher, b"Hello, ");
+        Digest::update(&mut hasher, b"world!");
+        assert_eq!(hasher.finalize_reset()[..], expected);
+
+        Digest::update(&mut hasher, b"Hello, world!");
+        assert_eq!(hasher.finalize()[..], expected);
+    }
+
+    #[test]
+    fn rustcrypto_digest() {
+        digest_matches::<AsconHash>(AsconHash::digest(b"Hello, world!"));
+        digest_matches::<AsconHasha>(AsconHasha::digest(b"Hello, world!"));
+    }
+
+    #[test]
+    fn rustcrypto_xof() {
+        let mut expected = [0u8; 40];
+        let mut xof = AsconXof::new();
+        xof.update(b"Hello, world!");
+        xof.finalize_xof().squeeze(&mut expected);
+
+        let mut out = [0u8; 40];
+        AsconXof::digest_xof(b"Hello, world!", &mut out);
+        assert_eq!(out, expected);
+
+        let mut xof = AsconCxof128::new(b"app").unwrap();
+        Update::update(&mut xof, b"Hello, world!");
+        let mut reader = ExtendableOutput::finalize_xof(xof);
+        reader.read(&mut out[..3]);
+        reader.read(&mut out[3..]);
+
+        let mut xof = AsconCxof128::new(b"app").unwrap();
+        xof.update(b"Hello, world!");
+        xof.finalize_xof().squeeze(&mut expected);
+        assert_eq!(out, expected);
+    }
+}
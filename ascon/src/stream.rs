@@ -0,0 +1,219 @@
+use core::fmt;
+
+use crate::ascon_128::{Ascon128, Key};
+use crate::Error;
+
+/// State shared by [`AsconEncryptor`] and [`AsconDecryptor`].
+///
+/// `position` counts the bytes already processed in the current block of the associated data or
+/// the message.
+struct Stream {
+    cipher: Ascon128,
+    position: usize,
+    empty_ad: bool,
+    in_message: bool,
+}
+
+impl Stream {
+    fn new(key: &Key, nonce: &[u8; 16]) -> Self {
+        Self {
+            cipher: Ascon128::new(key, nonce),
+            position: 0,
+            empty_ad: true,
+            in_message: false,
+        }
+    }
+
+    fn update_ad(&mut self, ad: &[u8]) -> Result<(), Error> {
+        if self.in_message {
+            return Err(Error::AssociatedDataAfterMessage);
+        }
+
+        self.empty_ad &= ad.is_empty();
+        self.position = self
+            .cipher
+            .absorb_associated_data_fragment(self.position, ad);
+        Ok(())
+    }
+
+    fn start_message(&mut self) {
+        if !self.in_message {
+            self.cipher
+                .finish_associated_data(self.position, self.empty_ad);
+            self.position = 0;
+            self.in_message = true;
+        }
+    }
+
+    fn update(&mut self, buffer: &mut [u8], decrypt: bool) {
+        self.start_message();
+        self.position = self.cipher.crypt_fragment(self.position, buffer, decrypt);
+    }
+
+    fn finalize(mut self) -> [u8; 16] {
+        self.start_message();
+        self.cipher.finish_message(self.position)
+    }
+}
+
+/// Encrypts a message given in fragments of any length, producing the same ciphertext and tag as
+/// [`AsconHead::encrypt`](crate::AsconHead::encrypt).
+///
+/// Created with [`AsconHead::encryptor`](crate::AsconHead::encryptor).
+pub struct AsconEncryptor {
+    stream: Stream,
+}
+
+impl AsconEncryptor {
+    pub(crate) fn new(key: &Key, nonce: &[u8; 16]) -> Self {
+        Self {
+            stream: Stream::new(key, nonce),
+        }
+    }
+
+    /// Absorb the next fragment of the associated data.
+    ///
+    /// Fails with [`Error::AssociatedDataAfterMessage`] once the message has started.
+    pub fn update_ad(&mut self, ad: &[u8]) -> Result<(), Error> {
+        self.stream.update_ad(ad)
+    }
+
+    /// Encrypt the next fragment of the message in place.
+    pub fn update(&mut self, buffer: &mut [u8]) {
+        self.stream.update(buffer, false);
+    }
+
+    /// Return the tag of the associated data and message.
+    pub fn finalize(self) -> [u8; 16] {
+        self.stream.finalize()
+    }
+}
+
+impl fmt::Debug for AsconEncryptor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AsconEncryptor").finish_non_exhaustive()
+    }
+}
+
+/// Decrypts a message given in fragments of any length.
+///
+/// The plaintext is handed back before the tag can be checked, so it must not be trusted until
+/// [`finalize`](Self::finalize) succeeds.
+///
+/// Created with [`AsconHead::decryptor`](crate::AsconHead::decryptor).
+pub struct AsconDecryptor {
+    stream: Stream,
+}
+
+impl AsconDecryptor {
+    pub(crate) fn new(key: &Key, nonce: &[u8; 16]) -> Self {
+        Self {
+            stream: Stream::new(key, nonce),
+        }
+    }
+
+    /// Absorb the next fragment of the associated data.
+    ///
+    /// Fails with [`Error::AssociatedDataAfterMessage`] once the message has started.
+    pub fn update_ad(&mut self, ad: &[u8]) -> Result<(), Error> {
+        self.stream.update_ad(ad)
+    }
+
+    /// Decrypt the next fragment of the message in place.
+    pub fn update(&mut self, buffer: &mut [u8]) {
+        self.stream.update(buffer, true);
+    }
+
+    /// Check the tag of the associated data and message.
+    pub fn finalize(self, expected_tag: &[u8]) -> Result<(), Error> {
+        if expected_tag.len() != 16 {
+            return Err(Error::InvalidTagLength);
+        }
+
+        lwc_core::verify_tag(&self.stream.finalize(), expected_tag)
+    }
+}
+
+impl fmt::Debug for AsconDecryptor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AsconDecryptor").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AsconHead;
+
+    const KEY: [u8; 16] = [0x42; 16];
+    const NONCE: [u8; 16] = [0x24; 16];
+
+    #[test]
+    fn matches_one_shot() {
+        let cipher = AsconHead::new(Key::from(&KEY));
+        let ad: [u8; 20] = core::array::from_fn(|i| i as u8);
+        let plaintext: [u8; 37] = core::array::from_fn(|i| (3 * i) as u8);
+
+        let mut expected = plaintext;
+        let expected_tag = cipher.encrypt(&NONCE, &ad, &mut expected);
+
+        for split in [0, 1, 3, 4, 17] {
+            let mut encryptor = cipher.encryptor(&NONCE);
+            for chunk in ad.chunks(split.max(1)) {
+                encryptor.update_ad(chunk).unwrap();
+            }
+            let mut buffer = plaintext;
+            let (head, tail) = buffer.split_at_mut(split);
+            encryptor.update(head);
+            encryptor.update(&mut []);
+            encryptor.update(tail);
+            assert_eq!(buffer, expected);
+            assert_eq!(encryptor.finalize()[..], expected_tag);
+
+            let mut decryptor = cipher.decryptor(&NONCE);
+            decryptor.update_ad(&ad).unwrap();
+            for chunk in buffer.chunks_mut(split.max(1)) {
+                decryptor.update(chunk);
+            }
+            assert_eq!(buffer, plaintext);
+            assert_eq!(decryptor.finalize(&expected_tag), Ok(()));
+        }
+    }
+
+    #[test]
+    fn empty_message() {
+        let cipher = AsconHead::new(Key::from(&KEY));
+        let expected_tag = cipher.encrypt(&NONCE, b"header", &mut []);
+
+        let mut encryptor = cipher.encryptor(&NONCE);
+        encryptor.update_ad(b"head").unwrap();
+        encryptor.update_ad(b"er").unwrap();
+        assert_eq!(encryptor.finalize()[..], expected_tag);
+    }
+
+    #[test]
+    fn forged_tag() {
+        let cipher = AsconHead::new(Key::from(&KEY));
+        let mut buffer = *b"message";
+        let mut tag = cipher.encrypt(&NONCE, &[], &mut buffer);
+        tag[0] ^= 1;
+
+        let mut decryptor = cipher.decryptor(&NONCE);
+        decryptor.update(&mut buffer);
+        assert_eq!(
+            cipher.decryptor(&NONCE).finalize(&tag[..8]),
+            Err(Error::InvalidTagLength)
+        );
+        assert_eq!(decryptor.finalize(&tag), Err(Error::AuthenticationFailed));
+    }
+
+    #[test]
+    fn late_associated_data() {
+        let mut encryptor = AsconHead::new(Key::from(&KEY)).encryptor(&NONCE);
+        encryptor.update(&mut [0u8; 4]);
+        assert_eq!(
+            encryptor.update_ad(b"header"),
+            Err(Error::AssociatedDataAfterMessage)
+        );
+    }
+}
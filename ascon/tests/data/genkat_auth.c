@@ -0,0 +1,166 @@
+/*
+ * Generator of asconmac.txt, asconprf.txt and asconprfs.txt: a transcription of Ascon-Mac,
+ * Ascon-Prf and Ascon-PrfShort as specified in "Ascon PRF, MAC, and Short-Input MAC" (Dobraunig,
+ * Eichlseder, Mendel, Schläffer, 2021) and implemented by crypto_auth/asconmacv12,
+ * crypto_auth/asconprfv12 and crypto_auth/asconprfsv12 of ascon-c, driven the way the genkat
+ * programs of ascon-c drive crypto_auth.
+ *
+ * The permutation is written from the specification and shares no code with the crate. The `hash`
+ * mode runs Ascon-Hash v1.2 on the same permutation and reproduces asconhash.txt, the reference
+ * LWC_HASH_KAT_256.txt, which checks the permutation and the byte order against published data.
+ *
+ *     cc -O1 -o genkat_auth genkat_auth.c
+ *     ./genkat_auth mac > asconmac.txt
+ *     ./genkat_auth prf > asconprf.txt
+ *     ./genkat_auth prfs > asconprfs.txt
+ *     ./genkat_auth hash | cmp - asconhash.txt
+ */
+
+#include <stdint.h>
+#include <stdio.h>
+#include <string.h>
+
+#define MAX_MESSAGE_LENGTH 1024
+#define PRFS_MAX_MESSAGE_LENGTH 16
+
+#define MAC_IV 0x80808c0000000080ull
+#define PRF_IV 0x80808c0000000000ull
+#define PRFS_IV 0x80004c8000000000ull
+#define HASH_IV 0x00400c0000000100ull
+
+static uint64_t ror(uint64_t x, int n) { return x >> n | x << (64 - n); }
+
+static void permute_12(uint64_t x[5]) {
+    for (int r = 0; r < 12; r++) {
+        uint64_t t[5];
+
+        /* Round constant. */
+        x[2] ^= (uint64_t)(((0xf - r) << 4) | r);
+
+        /* Substitution layer, the bitsliced 5-bit S-box. */
+        x[0] ^= x[4];
+        x[4] ^= x[3];
+        x[2] ^= x[1];
+        for (int i = 0; i < 5; i++) t[i] = ~x[i] & x[(i + 1) % 5];
+        for (int i = 0; i < 5; i++) x[i] ^= t[(i + 1) % 5];
+        x[1] ^= x[0];
+        x[0] ^= x[4];
+        x[3] ^= x[2];
+        x[2] = ~x[2];
+
+        /* Linear diffusion layer. */
+        x[0] ^= ror(x[0], 19) ^ ror(x[0], 28);
+        x[1] ^= ror(x[1], 61) ^ ror(x[1], 39);
+        x[2] ^= ror(x[2], 1) ^ ror(x[2], 6);
+        x[3] ^= ror(x[3], 10) ^ ror(x[3], 17);
+        x[4] ^= ror(x[4], 7) ^ ror(x[4], 41);
+    }
+}
+
+/* Big-endian load of up to 8 bytes into the top of a word, zero padded. */
+static uint64_t load(const uint8_t *bytes, size_t len) {
+    uint64_t word = 0;
+    for (size_t i = 0; i < len; i++) word |= (uint64_t)bytes[i] << (56 - 8 * i);
+    return word;
+}
+
+static void store(uint8_t *bytes, uint64_t word) {
+    for (int i = 0; i < 8; i++) bytes[i] = (uint8_t)(word >> (56 - 8 * i));
+}
+
+/* Ascon-Mac and Ascon-Prf with a 128-bit output: 256-bit input rate, 10* padding. */
+static void mac(uint8_t tag[16], const uint8_t *m, size_t mlen, const uint8_t key[16],
+                uint64_t iv) {
+    uint64_t x[5] = {iv, load(key, 8), load(key + 8, 8), 0, 0};
+    uint8_t block[32];
+
+    permute_12(x);
+    for (; mlen >= 32; m += 32, mlen -= 32) {
+        for (int i = 0; i < 4; i++) x[i] ^= load(m + 8 * i, 8);
+        permute_12(x);
+    }
+
+    memset(block, 0, sizeof block);
+    memcpy(block, m, mlen);
+    block[mlen] = 0x80;
+    for (int i = 0; i < 4; i++) x[i] ^= load(block + 8 * i, 8);
+    x[4] ^= 1;
+    permute_12(x);
+
+    store(tag, x[0]);
+    store(tag + 8, x[1]);
+}
+
+/* Ascon-PrfShort: the message and its length in bits go into the initial state. */
+static void prfs(uint8_t tag[16], const uint8_t *m, size_t mlen, const uint8_t key[16]) {
+    uint64_t k0 = load(key, 8), k1 = load(key + 8, 8);
+    uint64_t x[5] = {PRFS_IV | (uint64_t)(8 * mlen) << 48, k0, k1,
+                     load(m, mlen < 8 ? mlen : 8), load(m + 8, mlen > 8 ? mlen - 8 : 0)};
+
+    permute_12(x);
+    store(tag, x[3] ^ k0);
+    store(tag + 8, x[4] ^ k1);
+}
+
+/* Ascon-Hash v1.2: 64-bit rate, 10* padding and 256 bits of output. */
+static void hash(uint8_t digest[32], const uint8_t *m, size_t mlen) {
+    uint64_t x[5] = {HASH_IV, 0, 0, 0, 0};
+    uint8_t block[8];
+
+    permute_12(x);
+    for (; mlen >= 8; m += 8, mlen -= 8) {
+        x[0] ^= load(m, 8);
+        permute_12(x);
+    }
+
+    memset(block, 0, sizeof block);
+    memcpy(block, m, mlen);
+    block[mlen] = 0x80;
+    x[0] ^= load(block, 8);
+    for (int i = 0; i < 4; i++) {
+        permute_12(x);
+        store(digest + 8 * i, x[0]);
+    }
+}
+
+static void print_hex(const char *name, const uint8_t *data, size_t len) {
+    printf("%s = ", name);
+    for (size_t i = 0; i < len; i++) printf("%02X", data[i]);
+    printf("\n");
+}
+
+int main(int argc, char **argv) {
+    uint8_t key[16], msg[MAX_MESSAGE_LENGTH], tag[32];
+    const char *mode = argc == 2 ? argv[1] : "";
+    size_t max_mlen = MAX_MESSAGE_LENGTH;
+
+    if (!strcmp(mode, "prfs")) {
+        max_mlen = PRFS_MAX_MESSAGE_LENGTH;
+    } else if (strcmp(mode, "mac") && strcmp(mode, "prf") && strcmp(mode, "hash")) {
+        fprintf(stderr, "usage: %s mac|prf|prfs|hash\n", argv[0]);
+        return 1;
+    }
+
+    for (int i = 0; i < 16; i++) key[i] = i;
+    for (int i = 0; i < MAX_MESSAGE_LENGTH; i++) msg[i] = i;
+
+    for (size_t mlen = 0; mlen <= max_mlen; mlen++) {
+        printf("Count = %zu\n", mlen + 1);
+        if (!strcmp(mode, "hash")) {
+            hash(tag, msg, mlen);
+            print_hex("Msg", msg, mlen);
+            print_hex("MD", tag, 32);
+        } else {
+            if (!strcmp(mode, "prfs")) {
+                prfs(tag, msg, mlen, key);
+            } else {
+                mac(tag, msg, mlen, key, strcmp(mode, "mac") ? PRF_IV : MAC_IV);
+            }
+            print_hex("Key", key, 16);
+            print_hex("Msg", msg, mlen);
+            print_hex("Tag", tag, 16);
+        }
+        printf("\n");
+    }
+    return 0;
+}
@@ -0,0 +1,122 @@
+//! Known-answer tests for the hash and extendable-output functions.
+//!
+//! The Ascon v1.2 vectors are the `LWC_HASH_KAT_256.txt` files of the Ascon reference
+//! implementation. The SP 800-232 vectors are the `LWC_XOF_KAT_128_512.txt` and
+//! `LWC_CXOF_KAT_128_512.txt` files of `asconxof128` and `asconcxof128`, and
+//! `acvp_xof128.txt` holds the byte-aligned XOF128 and CXOF128 cases of the NIST ACVP examples.
+
+use ascon::{
+    AsconCxof128, AsconHash, AsconHasha, AsconXof, AsconXof128, AsconXofReader, AsconXofa,
+};
+use lwc_core::kat;
+
+fn run_kats(input: &str, expected_count: usize, digest: impl Fn(&[u8]) -> [u8; 32]) {
+    let vectors = kat::parse(input);
+    assert_eq!(vectors.len(), expected_count);
+
+    for vector in vectors {
+        let message = vector.bytes("Msg");
+        assert_eq!(
+            digest(&message)[..],
+            vector.bytes("MD"),
+            "digest of vector {}",
+            vector.count()
+        );
+    }
+}
+
+#[test]
+fn asconhash() {
+    run_kats(include_str!("data/asconhash.txt"), 1025, |message| {
+        // Feed the message in uneven pieces to exercise the block buffering.
+        let mut hasher = AsconHash::new();
+        for chunk in message.chunks(5) {
+            hasher.update(chunk);
+        }
+        hasher.finalize()
+    });
+}
+
+#[test]
+fn asconhasha() {
+    run_kats(
+        include_str!("data/asconhasha.txt"),
+        1025,
+        AsconHasha::digest,
+    );
+}
+
+/// Check the `MD` field of every vector against the output of `reader`, read in uneven pieces.
+fn run_xof_kats(
+    input: &str,
+    expected_count: usize,
+    reader: impl Fn(&kat::Vector) -> AsconXofReader,
+) {
+    let vectors = kat::parse(input);
+    assert_eq!(vectors.len(), expected_count);
+
+    for vector in vectors {
+        let expected = vector.bytes("MD");
+        let mut output = vec![0u8; expected.len()];
+        let mut reader = reader(&vector);
+        for chunk in output.chunks_mut(7) {
+            reader.squeeze(chunk);
+        }
+        let name = vector.get("Count").or(vector.get("TcId")).unwrap();
+        assert_eq!(output, expected, "output of vector {name}");
+    }
+}
+
+#[test]
+fn asconxof() {
+    run_xof_kats(include_str!("data/asconxof.txt"), 1025, |vector| {
+        let mut xof = AsconXof::new();
+        xof.update(&vector.bytes("Msg"));
+        xof.finalize_xof()
+    });
+}
+
+#[test]
+fn asconxofa() {
+    run_xof_kats(include_str!("data/asconxofa.txt"), 1025, |vector| {
+        let mut xof = AsconXofa::new();
+        xof.update(&vector.bytes("Msg"));
+        xof.finalize_xof()
+    });
+}
+
+/// Ascon-XOF128 for records without a `Z` field, Ascon-CXOF128 for the others.
+fn xof128(vector: &kat::Vector) -> AsconXofReader {
+    let message = vector.bytes("Msg");
+    match vector.get("Z") {
+        None => {
+            let mut xof = AsconXof128::new();
+            for chunk in message.chunks(5) {
+                xof.update(chunk);
+            }
+            xof.finalize_xof()
+        }
+        Some(_) => {
+            let mut xof = AsconCxof128::new(&vector.bytes("Z")).unwrap();
+            for chunk in message.chunks(5) {
+                xof.update(chunk);
+            }
+            xof.finalize_xof()
+        }
+    }
+}
+
+#[test]
+fn ascon_xof128() {
+    run_xof_kats(include_str!("data/xof128.txt"), 1025, xof128);
+}
+
+#[test]
+fn ascon_cxof128() {
+    run_xof_kats(include_str!("data/cxof128.txt"), 1089, xof128);
+}
+
+#[test]
+fn acvp_xof128() {
+    run_xof_kats(include_str!("data/acvp_xof128.txt"), 4, xof128);
+}
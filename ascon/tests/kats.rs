@@ -0,0 +1,121 @@
+//! Known-answer tests against the `LWC_AEAD_KAT_*.txt` files of the Ascon reference implementation.
+//!
+//! `ascon_aead128.txt` comes from `crypto_aead/asconaead128` of the reference implementation and
+//! covers SP 800-232; the other files cover the Ascon v1.2 submission.
+//!
+//! The files only hold valid ciphertexts, so every vector is also decrypted with a flipped bit in
+//! the ciphertext, the tag and the associated data, each of which must fail. The NIST ACVP
+//! examples of Ascon-AEAD128 would add such cases, but none of them has byte-aligned inputs, a
+//! full 128-bit tag and nonce masking off, the only combination this API supports.
+
+use ascon::{Ascon128aHead, Ascon80pqHead, AsconAead128Head, AsconHead};
+use lwc_core::{kat, Aead, Error};
+
+fn run_kats<A: Aead>(input: &str, expected_count: usize) {
+    let vectors = kat::parse(input);
+    assert_eq!(vectors.len(), expected_count);
+
+    for vector in vectors {
+        let cipher = A::new_from_slice(&vector.bytes("Key")).unwrap();
+        let nonce = vector.bytes("Nonce");
+        let associated_data = vector.bytes("AD");
+        let plaintext = vector.bytes("PT");
+        let ciphertext = vector.bytes("CT");
+
+        let mut buffer = plaintext.clone();
+        cipher
+            .encrypt_inplace(&nonce, &associated_data, &mut buffer)
+            .unwrap();
+        assert_eq!(
+            buffer,
+            ciphertext,
+            "encryption of vector {}",
+            vector.count()
+        );
+
+        cipher
+            .decrypt_inplace(&nonce, &associated_data, &mut buffer)
+            .unwrap_or_else(|err| panic!("decryption of vector {}: {err}", vector.count()));
+        assert_eq!(buffer, plaintext, "decryption of vector {}", vector.count());
+
+        // The first byte of the ciphertext, or of the tag when there is no plaintext, and the last
+        // byte of the tag.
+        for position in [0, ciphertext.len() - 1] {
+            let mut forged = ciphertext.clone();
+            forged[position] ^= 1;
+            assert_eq!(
+                cipher.decrypt_inplace(&nonce, &associated_data, &mut forged),
+                Err(Error::AuthenticationFailed),
+                "forged byte {position} of vector {}",
+                vector.count()
+            );
+            assert!(forged[..plaintext.len()].iter().all(|&byte| byte == 0));
+        }
+        if let Some((last, rest)) = associated_data.split_last() {
+            let mut forged_associated_data = rest.to_vec();
+            forged_associated_data.push(last ^ 0x80);
+            let mut buffer = ciphertext.clone();
+            assert_eq!(
+                cipher.decrypt_inplace(&nonce, &forged_associated_data, &mut buffer),
+                Err(Error::AuthenticationFailed),
+                "forged associated data of vector {}",
+                vector.count()
+            );
+        }
+    }
+}
+
+#[test]
+fn ascon128() {
+    run_kats::<AsconHead>(include_str!("data/ascon128.txt"), 1089);
+}
+
+#[test]
+fn ascon128a() {
+    run_kats::<Ascon128aHead>(include_str!("data/ascon128a.txt"), 1089);
+}
+
+#[test]
+fn ascon80pq() {
+    run_kats::<Ascon80pqHead>(include_str!("data/ascon80pq.txt"), 1089);
+}
+
+#[test]
+fn ascon_aead128() {
+    run_kats::<AsconAead128Head>(include_str!("data/ascon_aead128.txt"), 1089);
+}
+
+#[test]
+fn ascon128_streaming() {
+    // Feed the associated data and the message in uneven fragments.
+    for vector in kat::parse(include_str!("data/ascon128.txt")) {
+        let count = vector.count();
+        let cipher = AsconHead::new_from_slice(&vector.bytes("Key")).unwrap();
+        let nonce = vector.bytes("Nonce").try_into().unwrap();
+        let associated_data = vector.bytes("AD");
+        let plaintext = vector.bytes("PT");
+        let expected = vector.bytes("CT");
+        let (ciphertext, tag) = expected.split_at(plaintext.len());
+
+        let mut encryptor = cipher.encryptor(&nonce);
+        for chunk in associated_data.chunks(3) {
+            encryptor.update_ad(chunk).unwrap();
+        }
+        let mut buffer = plaintext.clone();
+        for chunk in buffer.chunks_mut(5) {
+            encryptor.update(chunk);
+        }
+        assert_eq!(buffer, ciphertext, "encryption of vector {count}");
+        assert_eq!(encryptor.finalize()[..], *tag, "tag of vector {count}");
+
+        let mut decryptor = cipher.decryptor(&nonce);
+        for chunk in associated_data.chunks(7) {
+            decryptor.update_ad(chunk).unwrap();
+        }
+        for chunk in buffer.chunks_mut(11) {
+            decryptor.update(chunk);
+        }
+        assert_eq!(buffer, plaintext, "decryption of vector {count}");
+        assert_eq!(decryptor.finalize(tag), Ok(()), "tag of vector {count}");
+    }
+}
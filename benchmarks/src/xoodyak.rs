@@ -8,16 +8,16 @@ use xoodyak::XoodyakAead;
 const KB: usize = 1024;
 
 fn bench_for_size_inplace(b: &mut Bencher, rng: &mut dyn RngCore, size: usize) {
-    let mut key = vec![0u8; 16];
-    rng.fill_bytes(key.as_mut_slice());
-    let mut nonce = vec![0u8; 16];
-    rng.fill_bytes(nonce.as_mut_slice());
+    let mut key = [0u8; 16];
+    rng.fill_bytes(&mut key);
+    let mut nonce = [0u8; 16];
+    rng.fill_bytes(&mut nonce);
     let mut buffer = vec![0u8; size];
     rng.fill_bytes(buffer.as_mut_slice());
 
-    let mut cipher = XoodyakAead::new(key.as_slice());
+    let cipher = XoodyakAead::new(&key);
 
-    b.iter(|| black_box(cipher.encrypt(nonce.as_mut_slice(), &[], buffer.as_mut_slice())));
+    b.iter(|| black_box(cipher.encrypt(&nonce, &[], buffer.as_mut_slice())));
 }
 
 fn criterion_benchmark_inplace(c: &mut Criterion) {
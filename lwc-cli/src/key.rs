@@ -0,0 +1,78 @@
+use std::path::PathBuf;
+use std::{env, fs};
+
+use clap::Args;
+
+use crate::Result;
+
+/// Environment variable the key is read from when no other source is given.
+pub const DEFAULT_KEY_ENV: &str = "LWC_KEY";
+
+/// Where to read a key from. Keys are stored in hexadecimal, as written by `lwc keygen`.
+#[derive(Args, Debug)]
+pub struct KeySource {
+    /// Read the key from this file
+    #[arg(long, value_name = "PATH", conflicts_with = "key_env")]
+    key_file: Option<PathBuf>,
+
+    /// Read the key from this environment variable [default: LWC_KEY]
+    #[arg(long, value_name = "NAME")]
+    key_env: Option<String>,
+}
+
+impl KeySource {
+    pub fn load(&self) -> Result<Vec<u8>> {
+        let (text, source) = match (&self.key_file, &self.key_env) {
+            (Some(path), _) => {
+                let text = fs::read_to_string(path)
+                    .map_err(|error| format!("cannot read key file {}: {error}", path.display()))?;
+                (text, path.display().to_string())
+            }
+            (None, Some(name)) => {
+                let text = env::var(name)
+                    .map_err(|_| format!("environment variable {name} is not set"))?;
+                (text, name.clone())
+            }
+            (None, None) => {
+                let text = env::var(DEFAULT_KEY_ENV).map_err(|_| {
+                    format!("no key given: use --key-file or --key-env, or set {DEFAULT_KEY_ENV}")
+                })?;
+                (text, String::from(DEFAULT_KEY_ENV))
+            }
+        };
+
+        decode_hex(text.trim())
+            .ok_or_else(|| format!("the key in {source} is not hexadecimal").into())
+    }
+}
+
+pub fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+pub fn decode_hex(text: &str) -> Option<Vec<u8>> {
+    if !text.len().is_multiple_of(2) || !text.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    text.as_bytes()
+        .chunks(2)
+        .map(|pair| u8::from_str_radix(std::str::from_utf8(pair).ok()?, 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hex() {
+        assert_eq!(encode_hex(&[0x00, 0xab, 0x7f]), "00ab7f");
+        assert_eq!(decode_hex("00AB7f"), Some(vec![0x00, 0xab, 0x7f]));
+        assert_eq!(decode_hex(""), Some(vec![]));
+        assert_eq!(decode_hex("abc"), None);
+        assert_eq!(decode_hex("zz"), None);
+        assert_eq!(decode_hex("+1"), None);
+        assert_eq!(decode_hex("é0"), None);
+    }
+}
@@ -0,0 +1,456 @@
+//! `lwc`, a command-line tool to encrypt, decrypt, hash and authenticate files with the ciphers of
+//! the workspace.
+//!
+//! Encrypted files are [`lwc_container`] containers, so `decrypt` needs only the key: the
+//! algorithm, nonce and chunk size are read from the header. Every command reads standard input and
+//! writes standard output unless given files, so they can be used in pipelines.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+use ascon::{AsconHash, AsconHasha, AsconMac, AsconPrf, Key};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use key::{encode_hex, KeySource};
+use lwc_container::{Algorithm, Header};
+use rand::rngs::OsRng;
+use rand::RngCore;
+use xoodyak::XoodyakHash;
+
+mod key;
+
+/// Error type of the commands, printed to standard error by `main`.
+type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+#[derive(Parser, Debug)]
+#[command(
+    name = "lwc",
+    version,
+    about = "Encrypt, decrypt, hash and authenticate files with lightweight ciphers"
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Encrypt a file into an LWC container
+    Encrypt {
+        /// Cipher to encrypt with: acorn128, ascon128, ascon128a, ascon80pq, ascon-aead128 or
+        /// xoodyak
+        #[arg(short, long, value_parser = parse_algorithm, default_value = "ascon128")]
+        algorithm: Algorithm,
+
+        #[command(flatten)]
+        key: KeySource,
+
+        /// Label stored in the header to tell which key was used
+        #[arg(long)]
+        key_id: Option<String>,
+
+        /// Size of the chunks authenticated one at a time, in bytes
+        #[arg(long, default_value_t = 64 * 1024)]
+        chunk_size: u32,
+
+        #[command(flatten)]
+        files: Files,
+    },
+
+    /// Decrypt an LWC container
+    ///
+    /// Nothing is written to the output file unless the whole container authenticates. When
+    /// writing to standard output, data is written as it is authenticated and must be discarded
+    /// if the command fails.
+    Decrypt {
+        #[command(flatten)]
+        key: KeySource,
+
+        #[command(flatten)]
+        files: Files,
+    },
+
+    /// Print the hash of a file in hexadecimal
+    Hash {
+        /// Hash function
+        #[arg(short, long, value_enum, default_value_t = HashAlgorithm::AsconHash)]
+        algorithm: HashAlgorithm,
+
+        /// Input file [default: standard input]
+        #[arg(short, long)]
+        input: Option<PathBuf>,
+    },
+
+    /// Print the MAC of a file in hexadecimal, or check it
+    Mac {
+        /// MAC function
+        #[arg(short, long, value_enum, default_value_t = MacAlgorithm::AsconMac)]
+        algorithm: MacAlgorithm,
+
+        #[command(flatten)]
+        key: KeySource,
+
+        /// Expected MAC in hexadecimal; the command fails if it does not match
+        #[arg(long, value_name = "MAC")]
+        verify: Option<String>,
+
+        /// Input file [default: standard input]
+        #[arg(short, long)]
+        input: Option<PathBuf>,
+    },
+
+    /// Generate a random key in hexadecimal
+    Keygen {
+        /// Cipher the key is for: acorn128, ascon128, ascon128a, ascon80pq, ascon-aead128 or
+        /// xoodyak
+        #[arg(short, long, value_parser = parse_algorithm, default_value = "ascon128")]
+        algorithm: Algorithm,
+
+        /// File to create, readable only by its owner [default: standard output]
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Measure encryption and decryption throughput
+    Bench {
+        /// Cipher to measure: acorn128, ascon128, ascon128a, ascon80pq, ascon-aead128 or xoodyak
+        /// [default: all]
+        #[arg(short, long, value_parser = parse_algorithm)]
+        algorithm: Option<Algorithm>,
+
+        /// Size of the message, in bytes
+        #[arg(long, default_value_t = 1 << 20)]
+        size: usize,
+    },
+}
+
+#[derive(Args, Debug)]
+struct Files {
+    /// Input file [default: standard input]
+    #[arg(short, long)]
+    input: Option<PathBuf>,
+
+    /// Output file [default: standard output]
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// Replace the output file if it already exists
+    #[arg(long)]
+    force: bool,
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum HashAlgorithm {
+    AsconHash,
+    AsconHasha,
+    Xoodyak,
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum MacAlgorithm {
+    AsconMac,
+    AsconPrf,
+}
+
+fn parse_algorithm(name: &str) -> std::result::Result<Algorithm, String> {
+    Algorithm::from_name(name).ok_or_else(|| {
+        let names: Vec<_> = Algorithm::ALL
+            .iter()
+            .map(|algorithm| algorithm.name())
+            .collect();
+        format!("expected one of {}", names.join(", "))
+    })
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match run(cli.command) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("lwc: {error}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(command: Command) -> Result<()> {
+    match command {
+        Command::Encrypt {
+            algorithm,
+            key,
+            key_id,
+            chunk_size,
+            files,
+        } => encrypt(algorithm, &key, key_id.as_deref(), chunk_size, &files),
+        Command::Decrypt { key, files } => decrypt(&key, &files),
+        Command::Hash { algorithm, input } => hash(algorithm, input.as_deref()),
+        Command::Mac {
+            algorithm,
+            key,
+            verify,
+            input,
+        } => mac(algorithm, &key, verify.as_deref(), input.as_deref()),
+        Command::Keygen { algorithm, output } => keygen(algorithm, output.as_deref()),
+        Command::Bench { algorithm, size } => bench(algorithm, size),
+    }
+}
+
+fn open_input(path: Option<&Path>) -> Result<Box<dyn Read>> {
+    match path {
+        Some(path) if path != Path::new("-") => {
+            let file = File::open(path)
+                .map_err(|error| format!("cannot open {}: {error}", path.display()))?;
+            Ok(Box::new(file))
+        }
+        _ => Ok(Box::new(io::stdin().lock())),
+    }
+}
+
+/// Run `write` on the output.
+///
+/// A file is written next to its path and only moved in place once `write` has succeeded, so that
+/// a failed run, such as a forged or truncated container, leaves nothing behind. An existing file
+/// is only replaced with `--force`.
+fn write_output<F>(files: &Files, write: F) -> Result<()>
+where
+    F: FnOnce(Box<dyn Write>) -> Result<Box<dyn Write>>,
+{
+    let path = match files.output.as_deref() {
+        Some(path) if path != Path::new("-") => path,
+        _ => {
+            write(Box::new(BufWriter::new(io::stdout().lock())))?.flush()?;
+            return Ok(());
+        }
+    };
+    if !files.force && fs::symlink_metadata(path).is_ok() {
+        return Err(format!(
+            "{} already exists, use --force to replace it",
+            path.display()
+        )
+        .into());
+    }
+
+    let partial = partial_path(path);
+    let file = create_new(&partial)?;
+    let result = write(Box::new(BufWriter::new(file))).and_then(|mut output| Ok(output.flush()?));
+    match result {
+        Ok(()) => fs::rename(&partial, path).map_err(Into::into),
+        Err(error) => {
+            let _ = fs::remove_file(&partial);
+            Err(error)
+        }
+    }
+}
+
+/// Create a file only the user can read, failing if anything already exists at `path`.
+fn create_new(path: &Path) -> Result<File> {
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    options
+        .open(path)
+        .map_err(|error| format!("cannot create {}: {error}", path.display()).into())
+}
+
+fn check_key_length(algorithm: Algorithm, key: &[u8]) -> Result<()> {
+    if key.len() != algorithm.key_size() {
+        return Err(format!(
+            "{} takes a {}-byte key, but the key has {} bytes",
+            algorithm.name(),
+            algorithm.key_size(),
+            key.len()
+        )
+        .into());
+    }
+    Ok(())
+}
+
+fn encrypt(
+    algorithm: Algorithm,
+    key: &KeySource,
+    key_id: Option<&str>,
+    chunk_size: u32,
+    files: &Files,
+) -> Result<()> {
+    let key = key.load()?;
+    check_key_length(algorithm, &key)?;
+
+    // With a random prefix, the nonces of different files only collide after about 2^44 files
+    // under the same key.
+    let mut nonce_prefix = vec![0u8; algorithm.nonce_prefix_size()];
+    OsRng.fill_bytes(&mut nonce_prefix);
+    let mut header = Header::new(algorithm, &nonce_prefix, chunk_size)?;
+    if let Some(key_id) = key_id {
+        header = header.with_key_id(key_id.as_bytes())?;
+    }
+
+    let input = open_input(files.input.as_deref())?;
+    write_output(files, |output| {
+        Ok(lwc_container::encrypt(&header, &key, input, output)?)
+    })
+}
+
+fn decrypt(key: &KeySource, files: &Files) -> Result<()> {
+    let key = key.load()?;
+    let mut input = open_input(files.input.as_deref())?;
+    let header = Header::read_from(&mut input)?;
+    check_key_length(header.algorithm(), &key)?;
+
+    write_output(files, |output| {
+        Ok(lwc_container::decrypt(&header, &key, input, output)?)
+    })
+    .map_err(|error| match error.downcast_ref() {
+        Some(lwc_container::Error::Cipher(
+            lwc_core::Error::AuthenticationFailed | lwc_core::Error::BufferTooSmall,
+        )) => "authentication failed: the container was modified or truncated, or the key is wrong"
+            .into(),
+        _ => error,
+    })
+}
+
+/// Path an output file is written to until it is complete.
+///
+/// The random suffix keeps a file left behind by a run that was killed from getting in the way of
+/// the next one.
+fn partial_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(".{:016x}.partial", OsRng.next_u64()));
+    path.with_file_name(name)
+}
+
+fn hash(algorithm: HashAlgorithm, input: Option<&Path>) -> Result<()> {
+    let mut input = open_input(input)?;
+    let mut buffer = vec![0u8; 64 * 1024];
+
+    let digest = match algorithm {
+        HashAlgorithm::AsconHash => {
+            let mut hasher = AsconHash::new();
+            read_chunks(&mut input, &mut buffer, |chunk| hasher.update(chunk))?;
+            hasher.finalize()
+        }
+        HashAlgorithm::AsconHasha => {
+            let mut hasher = AsconHasha::new();
+            read_chunks(&mut input, &mut buffer, |chunk| hasher.update(chunk))?;
+            hasher.finalize()
+        }
+        HashAlgorithm::Xoodyak => {
+            let mut hasher = XoodyakHash::new();
+            read_chunks(&mut input, &mut buffer, |chunk| hasher.update(chunk))?;
+            hasher.finalize()
+        }
+    };
+
+    println!("{}", encode_hex(&digest));
+    Ok(())
+}
+
+fn mac(
+    algorithm: MacAlgorithm,
+    key: &KeySource,
+    expected: Option<&str>,
+    input: Option<&Path>,
+) -> Result<()> {
+    let key = key.load()?;
+    let key = Key::try_from(key.as_slice())
+        .map_err(|_| format!("the MAC key must have 16 bytes, but it has {}", key.len()))?;
+    let mut input = open_input(input)?;
+    let mut buffer = vec![0u8; 64 * 1024];
+
+    let tag = match algorithm {
+        MacAlgorithm::AsconMac => {
+            let mut mac = AsconMac::new(&key);
+            read_chunks(&mut input, &mut buffer, |chunk| mac.update(chunk))?;
+            mac.finalize()
+        }
+        MacAlgorithm::AsconPrf => {
+            let mut prf = AsconPrf::new(&key);
+            read_chunks(&mut input, &mut buffer, |chunk| prf.update(chunk))?;
+            prf.finalize()
+        }
+    };
+
+    match expected {
+        None => println!("{}", encode_hex(&tag)),
+        Some(expected) => {
+            let expected =
+                key::decode_hex(expected.trim()).ok_or("the expected MAC is not hexadecimal")?;
+            lwc_core::verify_tag(&tag, &expected)
+                .map_err(|_| "MAC verification failed: the data or the key does not match")?;
+        }
+    }
+    Ok(())
+}
+
+fn read_chunks(input: &mut dyn Read, buffer: &mut [u8], mut f: impl FnMut(&[u8])) -> Result<()> {
+    loop {
+        match input.read(buffer) {
+            Ok(0) => return Ok(()),
+            Ok(len) => f(&buffer[..len]),
+            Err(error) if error.kind() == io::ErrorKind::Interrupted => {}
+            Err(error) => return Err(error.into()),
+        }
+    }
+}
+
+fn keygen(algorithm: Algorithm, output: Option<&Path>) -> Result<()> {
+    let mut key = vec![0u8; algorithm.key_size()];
+    OsRng.fill_bytes(&mut key);
+    let line = format!("{}\n", encode_hex(&key));
+
+    match output {
+        Some(path) if path != Path::new("-") => {
+            let mut file = create_new(path)?;
+            file.write_all(line.as_bytes())?;
+        }
+        _ => io::stdout().write_all(line.as_bytes())?,
+    }
+    Ok(())
+}
+
+fn bench(algorithm: Option<Algorithm>, size: usize) -> Result<()> {
+    let algorithms = match algorithm {
+        Some(algorithm) => vec![algorithm],
+        None => Algorithm::ALL.to_vec(),
+    };
+    let plaintext = vec![0x5au8; size];
+
+    for algorithm in algorithms {
+        let key = vec![0x42u8; algorithm.key_size()];
+        let nonce_prefix = vec![0x24u8; algorithm.nonce_prefix_size()];
+        let header = Header::new(algorithm, &nonce_prefix, 64 * 1024)?;
+
+        let mut container = Vec::new();
+        let encrypt = throughput(size, || {
+            container = lwc_container::seal(&header, &key, &plaintext)?;
+            Ok(())
+        })?;
+        let decrypt = throughput(size, || {
+            lwc_container::open(&key, &container)?;
+            Ok(())
+        })?;
+
+        println!(
+            "{:<14} encrypt {:>9.2} MiB/s   decrypt {:>9.2} MiB/s",
+            algorithm.name(),
+            encrypt,
+            decrypt
+        );
+    }
+    Ok(())
+}
+
+/// Run `f` for at least half a second and return how many MiB of `size` bytes it processed per
+/// second.
+fn throughput(size: usize, mut f: impl FnMut() -> Result<()>) -> Result<f64> {
+    let start = Instant::now();
+    let mut runs = 0u32;
+    while runs == 0 || start.elapsed() < Duration::from_millis(500) {
+        f()?;
+        runs += 1;
+    }
+    let bytes = size as f64 * runs as f64;
+    Ok(bytes / (1024.0 * 1024.0) / start.elapsed().as_secs_f64())
+}
@@ -0,0 +1,240 @@
+//! End-to-end tests of the `lwc` binary, run through pipes and temporary files.
+
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Output, Stdio};
+use std::{env, fs, thread};
+
+const KEY: &str = "000102030405060708090a0b0c0d0e0f";
+
+fn lwc(args: &[&str], stdin: &[u8]) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_lwc"))
+        .args(args)
+        .env("LWC_KEY", KEY)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    // Feed standard input from another thread, since the output pipe could fill up first.
+    let mut pipe = child.stdin.take().unwrap();
+    let stdin = stdin.to_vec();
+    let writer = thread::spawn(move || {
+        // The command may exit without reading everything, e.g. on an invalid header.
+        let _ = pipe.write_all(&stdin);
+    });
+    let output = child.wait_with_output().unwrap();
+    writer.join().unwrap();
+    output
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stderr).into_owned()
+}
+
+/// Directory for the files of one test, emptied first.
+fn scratch(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("lwc-cli-{}-{name}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn round_trip() {
+    let plaintext: Vec<u8> = (0..200_000u32).map(|i| i as u8).collect();
+    for algorithm in ["acorn128", "ascon128", "ascon128a", "xoodyak"] {
+        let encrypted = lwc(
+            &["encrypt", "-a", algorithm, "--chunk-size", "4096"],
+            &plaintext,
+        );
+        assert!(encrypted.status.success(), "{}", stderr(&encrypted));
+        assert!(encrypted.stdout.starts_with(b"LWC\0"));
+
+        let decrypted = lwc(&["decrypt"], &encrypted.stdout);
+        assert!(decrypted.status.success(), "{}", stderr(&decrypted));
+        assert_eq!(decrypted.stdout, plaintext, "{algorithm}");
+    }
+}
+
+#[test]
+fn tampered() {
+    let dir = scratch("tampered");
+    let mut container = lwc(&["encrypt"], b"attack at dawn").stdout;
+    let last = container.len() - 1;
+    container[last] ^= 1;
+    let input = dir.join("message.lwc");
+    fs::write(&input, &container).unwrap();
+
+    let output = dir.join("message.txt");
+    let decrypted = lwc(
+        &[
+            "decrypt",
+            "-i",
+            input.to_str().unwrap(),
+            "-o",
+            output.to_str().unwrap(),
+        ],
+        &[],
+    );
+    assert!(!decrypted.status.success());
+    assert!(stderr(&decrypted).contains("authentication failed"));
+    assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+
+    let truncated = lwc(&["decrypt"], &container[..container.len() - 20]);
+    assert!(!truncated.status.success());
+    assert!(stderr(&truncated).contains("authentication failed"));
+
+    let not_a_container = lwc(&["decrypt"], b"attack at dawn");
+    assert!(stderr(&not_a_container).contains("not an LWC container"));
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn output_files() {
+    let dir = scratch("output-files");
+    let container = dir.join("message.lwc");
+    let encrypted = lwc(
+        &["encrypt", "-o", container.to_str().unwrap()],
+        b"attack at dawn",
+    );
+    assert!(encrypted.status.success(), "{}", stderr(&encrypted));
+
+    let output = dir.join("message.txt");
+    let decrypted = lwc(
+        &[
+            "decrypt",
+            "-i",
+            container.to_str().unwrap(),
+            "-o",
+            output.to_str().unwrap(),
+        ],
+        &[],
+    );
+    assert!(decrypted.status.success(), "{}", stderr(&decrypted));
+    assert_eq!(fs::read(&output).unwrap(), b"attack at dawn");
+    #[cfg(unix)]
+    for path in [&container, &output] {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = fs::metadata(path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600, "{}", path.display());
+    }
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn existing_output() {
+    let dir = scratch("existing-output");
+    let output = dir.join("message.lwc");
+    fs::write(&output, b"precious").unwrap();
+    // Left behind by a run that was killed before it could clean up.
+    let stale = dir.join("message.lwc.partial");
+    fs::write(&stale, b"stale").unwrap();
+    let path = output.to_str().unwrap();
+
+    let refused = lwc(&["encrypt", "-o", path], b"attack at dawn");
+    assert!(!refused.status.success());
+    assert!(stderr(&refused).contains("already exists, use --force"));
+    assert_eq!(fs::read(&output).unwrap(), b"precious");
+
+    let forced = lwc(&["encrypt", "--force", "-o", path], b"attack at dawn");
+    assert!(forced.status.success(), "{}", stderr(&forced));
+    let decrypted = lwc(&["decrypt", "-i", path], &[]);
+    assert_eq!(decrypted.stdout, b"attack at dawn");
+
+    // The stale file neither blocked the output nor was touched, and nothing else is left.
+    assert_eq!(fs::read(&stale).unwrap(), b"stale");
+    assert_eq!(fs::read_dir(&dir).unwrap().count(), 2);
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn keys() {
+    let dir = scratch("keys");
+    let key_file = dir.join("ascon80pq.key");
+    let key_path = key_file.to_str().unwrap();
+
+    let keygen = lwc(&["keygen", "-a", "ascon80pq", "-o", key_path], &[]);
+    assert!(keygen.status.success(), "{}", stderr(&keygen));
+    assert_eq!(fs::read_to_string(&key_file).unwrap().trim().len(), 40);
+    // An existing key is never overwritten.
+    assert!(!lwc(&["keygen", "-o", key_path], &[]).status.success());
+
+    let encrypted = lwc(
+        &["encrypt", "-a", "ascon80pq", "--key-file", key_path],
+        b"data",
+    );
+    assert!(encrypted.status.success(), "{}", stderr(&encrypted));
+    let output = dir.join("data.txt");
+    let decrypted = lwc(
+        &[
+            "decrypt",
+            "--key-file",
+            key_path,
+            "-o",
+            output.to_str().unwrap(),
+        ],
+        &encrypted.stdout,
+    );
+    assert!(decrypted.status.success(), "{}", stderr(&decrypted));
+    assert_eq!(fs::read(&output).unwrap(), b"data");
+
+    // LWC_KEY holds a 16-byte key, which Ascon-80pq does not take.
+    let wrong_length = lwc(&["decrypt"], &encrypted.stdout);
+    assert!(stderr(&wrong_length).contains("ascon80pq takes a 20-byte key"));
+
+    let missing = lwc(&["encrypt", "--key-env", "LWC_TEST_UNSET"], b"data");
+    assert!(stderr(&missing).contains("LWC_TEST_UNSET is not set"));
+
+    let keygen = lwc(&["keygen", "-a", "xoodyak"], &[]);
+    assert_eq!(keygen.stdout.len(), 33);
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn hash() {
+    let expected = format!("{}\n", hex(&ascon::AsconHash::digest(b"abc")));
+    assert_eq!(lwc(&["hash"], b"abc").stdout, expected.as_bytes());
+
+    let expected = format!("{}\n", hex(&xoodyak::XoodyakHash::digest(b"abc")));
+    assert_eq!(
+        lwc(&["hash", "-a", "xoodyak"], b"abc").stdout,
+        expected.as_bytes()
+    );
+}
+
+#[test]
+fn mac() {
+    let output = lwc(&["mac"], b"message");
+    assert!(output.status.success(), "{}", stderr(&output));
+    let tag = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(tag.trim().len(), 32);
+
+    assert!(lwc(&["mac", "--verify", tag.trim()], b"message")
+        .status
+        .success());
+    let forged = lwc(&["mac", "--verify", tag.trim()], b"massage");
+    assert!(!forged.status.success());
+    assert!(stderr(&forged).contains("MAC verification failed"));
+}
+
+#[test]
+fn bench() {
+    let output = lwc(&["bench", "-a", "xoodyak", "--size", "1024"], &[]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    let report = String::from_utf8(output.stdout).unwrap();
+    assert!(report.starts_with("xoodyak"));
+    assert!(report.contains("MiB/s"));
+}
+
+#[test]
+fn unknown_algorithm() {
+    let output = lwc(&["encrypt", "-a", "aes"], b"");
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("acorn128, ascon128, ascon128a"));
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
@@ -0,0 +1,70 @@
+use std::{fmt, io};
+
+/// Errors returned when writing or reading a container.
+#[derive(Debug)]
+pub enum Error {
+    /// The data does not start with [`MAGIC`](crate::MAGIC).
+    InvalidMagic,
+    /// The container was written by a version of the format this crate does not read.
+    UnsupportedVersion(u8),
+    /// The algorithm ID is not one of [`Algorithm`](crate::Algorithm).
+    UnknownAlgorithm(u8),
+    /// The chunk size is zero or larger than [`MAX_CHUNK_SIZE`](crate::MAX_CHUNK_SIZE).
+    InvalidChunkSize,
+    /// The data ends inside the header.
+    TruncatedHeader,
+    /// The cipher rejected the key, nonce or key ID, or a segment did not authenticate.
+    Cipher(lwc_core::Error),
+    /// Reading the input or writing the output failed.
+    Io(io::Error),
+}
+
+/// Result type of the container functions.
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::InvalidMagic => f.write_str("not an LWC container"),
+            Error::UnsupportedVersion(version) => {
+                write!(f, "unsupported container version {version}")
+            }
+            Error::UnknownAlgorithm(id) => write!(f, "unknown algorithm ID {id}"),
+            Error::InvalidChunkSize => f.write_str("invalid chunk size"),
+            Error::TruncatedHeader => f.write_str("truncated container header"),
+            Error::Cipher(error) => error.fmt(f),
+            Error::Io(error) => error.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Cipher(error) => Some(error),
+            Error::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<lwc_core::Error> for Error {
+    fn from(error: lwc_core::Error) -> Self {
+        Error::Cipher(error)
+    }
+}
+
+impl From<io::Error> for Error {
+    /// Recover the cipher error that the [`DecryptReader`](lwc_core::DecryptReader) wraps in an
+    /// [`io::Error`], so that a forged segment is reported as such.
+    fn from(error: io::Error) -> Self {
+        let cipher_error = error
+            .get_ref()
+            .and_then(|inner| inner.downcast_ref::<lwc_core::Error>())
+            .copied();
+        match cipher_error {
+            Some(cipher_error) => Error::Cipher(cipher_error),
+            None => Error::Io(error),
+        }
+    }
+}
@@ -0,0 +1,334 @@
+use std::io::{self, Read};
+
+use lwc_core::{Aead, STREAM_NONCE_OVERHEAD};
+
+use crate::{Error, Result};
+
+/// First bytes of every container.
+pub const MAGIC: [u8; 4] = *b"LWC\0";
+
+/// Version of the format written by this crate.
+pub const VERSION: u8 = 1;
+
+/// Largest chunk size accepted, which bounds the memory a reader allocates for one segment.
+pub const MAX_CHUNK_SIZE: u32 = 1 << 24;
+
+/// Largest key ID in bytes.
+pub const MAX_KEY_ID_SIZE: usize = u8::MAX as usize;
+
+/// Cipher a container is encrypted with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Algorithm {
+    Acorn128,
+    Ascon128,
+    Ascon128a,
+    Ascon80pq,
+    AsconAead128,
+    Xoodyak,
+}
+
+/// Run `$body` with `$cipher` standing for the cipher type of `$algorithm`.
+macro_rules! with_cipher {
+    ($algorithm:expr, $cipher:ident => $body:expr) => {
+        match $algorithm {
+            Algorithm::Acorn128 => {
+                type $cipher = acorn::AcornHead;
+                $body
+            }
+            Algorithm::Ascon128 => {
+                type $cipher = ascon::AsconHead;
+                $body
+            }
+            Algorithm::Ascon128a => {
+                type $cipher = ascon::Ascon128aHead;
+                $body
+            }
+            Algorithm::Ascon80pq => {
+                type $cipher = ascon::Ascon80pqHead;
+                $body
+            }
+            Algorithm::AsconAead128 => {
+                type $cipher = ascon::AsconAead128Head;
+                $body
+            }
+            Algorithm::Xoodyak => {
+                type $cipher = xoodyak::XoodyakAead;
+                $body
+            }
+        }
+    };
+}
+pub(crate) use with_cipher;
+
+impl Algorithm {
+    /// Every algorithm, in the order of their IDs.
+    pub const ALL: [Algorithm; 6] = [
+        Algorithm::Acorn128,
+        Algorithm::Ascon128,
+        Algorithm::Ascon128a,
+        Algorithm::Ascon80pq,
+        Algorithm::AsconAead128,
+        Algorithm::Xoodyak,
+    ];
+
+    /// ID stored in the header. IDs are never reused for another algorithm.
+    pub fn id(self) -> u8 {
+        match self {
+            Algorithm::Acorn128 => 1,
+            Algorithm::Ascon128 => 2,
+            Algorithm::Ascon128a => 3,
+            Algorithm::Ascon80pq => 4,
+            Algorithm::AsconAead128 => 5,
+            Algorithm::Xoodyak => 6,
+        }
+    }
+
+    pub fn from_id(id: u8) -> Option<Self> {
+        Self::ALL.into_iter().find(|algorithm| algorithm.id() == id)
+    }
+
+    /// Lowercase name, such as `ascon128a`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Algorithm::Acorn128 => "acorn128",
+            Algorithm::Ascon128 => "ascon128",
+            Algorithm::Ascon128a => "ascon128a",
+            Algorithm::Ascon80pq => "ascon80pq",
+            Algorithm::AsconAead128 => "ascon-aead128",
+            Algorithm::Xoodyak => "xoodyak",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|algorithm| algorithm.name() == name)
+    }
+
+    /// Length of the key in bytes.
+    pub fn key_size(self) -> usize {
+        with_cipher!(self, C => C::KEY_SIZE)
+    }
+
+    /// Length of the nonce prefix in bytes, which leaves room for the STREAM counter and flag.
+    pub fn nonce_prefix_size(self) -> usize {
+        with_cipher!(self, C => C::NONCE_SIZE - STREAM_NONCE_OVERHEAD)
+    }
+
+    /// Length of the tag of each segment in bytes.
+    pub fn tag_size(self) -> usize {
+        with_cipher!(self, C => C::TAG_SIZE)
+    }
+}
+
+/// Header of a container, authenticated as the associated data of every segment.
+///
+/// It is laid out as follows, with integers in big-endian order:
+///
+/// | Bytes | Field                                         |
+/// |-------|-----------------------------------------------|
+/// | 4     | [`MAGIC`]                                     |
+/// | 1     | [`VERSION`]                                   |
+/// | 1     | [`Algorithm::id`]                             |
+/// | 4     | chunk size                                    |
+/// | 1     | nonce prefix length `n`                       |
+/// | `n`   | nonce prefix                                  |
+/// | 1     | key ID length `k`, zero when there is none    |
+/// | `k`   | key ID                                        |
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Header {
+    algorithm: Algorithm,
+    chunk_size: u32,
+    nonce_prefix: Vec<u8>,
+    key_id: Option<Vec<u8>>,
+}
+
+impl Header {
+    /// Describe a container of `algorithm` in chunks of `chunk_size` bytes.
+    ///
+    /// `nonce_prefix` must be [`Algorithm::nonce_prefix_size`] bytes long and never be used twice
+    /// with the same key.
+    pub fn new(algorithm: Algorithm, nonce_prefix: &[u8], chunk_size: u32) -> Result<Self> {
+        if nonce_prefix.len() != algorithm.nonce_prefix_size() {
+            return Err(Error::Cipher(lwc_core::Error::InvalidNonceLength));
+        }
+        if chunk_size == 0 || chunk_size > MAX_CHUNK_SIZE {
+            return Err(Error::InvalidChunkSize);
+        }
+
+        Ok(Self {
+            algorithm,
+            chunk_size,
+            nonce_prefix: nonce_prefix.to_vec(),
+            key_id: None,
+        })
+    }
+
+    /// Record which key the container is encrypted with, in 1 to [`MAX_KEY_ID_SIZE`] bytes.
+    pub fn with_key_id(mut self, key_id: &[u8]) -> Result<Self> {
+        if key_id.is_empty() || key_id.len() > MAX_KEY_ID_SIZE {
+            return Err(Error::Cipher(lwc_core::Error::InvalidKeyIdLength));
+        }
+
+        self.key_id = Some(key_id.to_vec());
+        Ok(self)
+    }
+
+    pub fn algorithm(&self) -> Algorithm {
+        self.algorithm
+    }
+
+    pub fn chunk_size(&self) -> u32 {
+        self.chunk_size
+    }
+
+    pub fn nonce_prefix(&self) -> &[u8] {
+        &self.nonce_prefix
+    }
+
+    pub fn key_id(&self) -> Option<&[u8]> {
+        self.key_id.as_deref()
+    }
+
+    /// Serialize the header.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let key_id = self.key_id().unwrap_or_default();
+        let mut bytes = Vec::with_capacity(12 + self.nonce_prefix.len() + key_id.len());
+        bytes.extend_from_slice(&MAGIC);
+        bytes.push(VERSION);
+        bytes.push(self.algorithm.id());
+        bytes.extend_from_slice(&self.chunk_size.to_be_bytes());
+        bytes.push(self.nonce_prefix.len() as u8);
+        bytes.extend_from_slice(&self.nonce_prefix);
+        bytes.push(key_id.len() as u8);
+        bytes.extend_from_slice(key_id);
+        bytes
+    }
+
+    /// Parse a header, leaving `reader` at the first segment.
+    pub fn read_from<R: Read>(reader: &mut R) -> Result<Self> {
+        let mut fixed = [0u8; 11];
+        read_exact(reader, &mut fixed)?;
+        let [m0, m1, m2, m3, version, algorithm, c0, c1, c2, c3, nonce_len] = fixed;
+
+        if [m0, m1, m2, m3] != MAGIC {
+            return Err(Error::InvalidMagic);
+        }
+        if version != VERSION {
+            return Err(Error::UnsupportedVersion(version));
+        }
+        let algorithm = Algorithm::from_id(algorithm).ok_or(Error::UnknownAlgorithm(algorithm))?;
+        let chunk_size = u32::from_be_bytes([c0, c1, c2, c3]);
+
+        let mut nonce_prefix = vec![0u8; nonce_len as usize];
+        read_exact(reader, &mut nonce_prefix)?;
+        let header = Self::new(algorithm, &nonce_prefix, chunk_size)?;
+
+        let mut key_id_len = [0u8; 1];
+        read_exact(reader, &mut key_id_len)?;
+        if key_id_len[0] == 0 {
+            return Ok(header);
+        }
+        let mut key_id = vec![0u8; key_id_len[0] as usize];
+        read_exact(reader, &mut key_id)?;
+        header.with_key_id(&key_id)
+    }
+}
+
+fn read_exact<R: Read>(reader: &mut R, buffer: &mut [u8]) -> Result<()> {
+    reader
+        .read_exact(buffer)
+        .map_err(|error| match error.kind() {
+            io::ErrorKind::UnexpectedEof => Error::TruncatedHeader,
+            _ => Error::Io(error),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn algorithms() {
+        for (index, algorithm) in Algorithm::ALL.into_iter().enumerate() {
+            assert_eq!(algorithm.id() as usize, index + 1);
+            assert_eq!(Algorithm::from_id(algorithm.id()), Some(algorithm));
+            assert_eq!(Algorithm::from_name(algorithm.name()), Some(algorithm));
+            assert_eq!(algorithm.nonce_prefix_size(), 11);
+        }
+        assert_eq!(Algorithm::Ascon80pq.key_size(), 20);
+        assert_eq!(Algorithm::from_id(0), None);
+        assert_eq!(Algorithm::from_name("aes"), None);
+    }
+
+    #[test]
+    fn round_trip() {
+        let header = Header::new(Algorithm::Xoodyak, &[7u8; 11], 4096).unwrap();
+        for header in [header.clone(), header.with_key_id(b"backup-2024").unwrap()] {
+            let bytes = header.to_bytes();
+            let mut reader = &bytes[..];
+            assert_eq!(Header::read_from(&mut reader).unwrap(), header);
+            assert!(reader.is_empty());
+        }
+    }
+
+    #[test]
+    fn invalid_fields() {
+        assert!(matches!(
+            Header::new(Algorithm::Ascon128, &[0u8; 12], 64),
+            Err(Error::Cipher(lwc_core::Error::InvalidNonceLength))
+        ));
+        for chunk_size in [0, MAX_CHUNK_SIZE + 1] {
+            assert!(matches!(
+                Header::new(Algorithm::Ascon128, &[0u8; 11], chunk_size),
+                Err(Error::InvalidChunkSize)
+            ));
+        }
+        let header = Header::new(Algorithm::Ascon128, &[0u8; 11], 64).unwrap();
+        assert!(header.clone().with_key_id(&[]).is_err());
+        assert!(header.with_key_id(&[0u8; MAX_KEY_ID_SIZE + 1]).is_err());
+    }
+
+    #[test]
+    fn parse_errors() {
+        let bytes = Header::new(Algorithm::Acorn128, &[1u8; 11], 64)
+            .unwrap()
+            .with_key_id(b"id")
+            .unwrap()
+            .to_bytes();
+        let parse = |bytes: &[u8]| Header::read_from(&mut &bytes[..]);
+
+        for len in 0..bytes.len() {
+            assert!(matches!(parse(&bytes[..len]), Err(Error::TruncatedHeader)));
+        }
+
+        let mut corrupted = bytes.clone();
+        corrupted[0] ^= 1;
+        assert!(matches!(parse(&corrupted), Err(Error::InvalidMagic)));
+
+        let mut corrupted = bytes.clone();
+        corrupted[4] = 2;
+        assert!(matches!(
+            parse(&corrupted),
+            Err(Error::UnsupportedVersion(2))
+        ));
+
+        let mut corrupted = bytes.clone();
+        corrupted[5] = 0xff;
+        assert!(matches!(
+            parse(&corrupted),
+            Err(Error::UnknownAlgorithm(0xff))
+        ));
+
+        let mut corrupted = bytes.clone();
+        corrupted[6..10].copy_from_slice(&[0; 4]);
+        assert!(matches!(parse(&corrupted), Err(Error::InvalidChunkSize)));
+
+        let mut corrupted = bytes;
+        corrupted[10] = 12;
+        assert!(matches!(
+            parse(&corrupted),
+            Err(Error::Cipher(lwc_core::Error::InvalidNonceLength))
+        ));
+    }
+}
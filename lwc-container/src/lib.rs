@@ -0,0 +1,145 @@
+//! Self-describing container for data encrypted at rest with the ciphers of the workspace.
+//!
+//! A container is a [`Header`] naming the algorithm, nonce prefix, chunk size and optionally the
+//! key ID, followed by the plaintext encrypted with [STREAM](lwc_core::StreamEncryptor) in
+//! segments of `chunk_size + tag_size` bytes, of which only the last can be shorter. The serialized
+//! header is the associated data of every segment, so none of its fields can be changed without
+//! the container failing to decrypt.
+
+use std::io::{self, Read, Write};
+
+pub use error::{Error, Result};
+use header::with_cipher;
+pub use header::{Algorithm, Header, MAGIC, MAX_CHUNK_SIZE, MAX_KEY_ID_SIZE, VERSION};
+use lwc_core::{Aead, DecryptReader, EncryptWriter};
+
+mod error;
+mod header;
+
+/// Write `header` followed by the encryption of everything read from `plaintext` to `container`,
+/// and return the writer.
+pub fn encrypt<R: Read, W: Write>(
+    header: &Header,
+    key: &[u8],
+    plaintext: R,
+    container: W,
+) -> Result<W> {
+    with_cipher!(header.algorithm(), C => encrypt_with::<C, _, _>(header, key, plaintext, container))
+}
+
+fn encrypt_with<C: Aead, R: Read, W: Write>(
+    header: &Header,
+    key: &[u8],
+    mut plaintext: R,
+    mut container: W,
+) -> Result<W> {
+    let cipher = C::new_from_slice(key)?;
+    let header_bytes = header.to_bytes();
+    container.write_all(&header_bytes)?;
+
+    let mut writer = EncryptWriter::new(
+        container,
+        cipher,
+        header.nonce_prefix(),
+        header.chunk_size() as usize,
+    )?
+    .with_associated_data(&header_bytes);
+    io::copy(&mut plaintext, &mut writer)?;
+    Ok(writer.finish()?)
+}
+
+/// Decrypt the segments that follow `header`, which was read from the start of `container` with
+/// [`Header::read_from`], into `plaintext`, and return the writer.
+///
+/// Each chunk is written as soon as it is authenticated, so on error `plaintext` may already hold
+/// part of the data; it must be discarded, since the container was truncated or tampered with.
+pub fn decrypt<R: Read, W: Write>(
+    header: &Header,
+    key: &[u8],
+    container: R,
+    plaintext: W,
+) -> Result<W> {
+    with_cipher!(header.algorithm(), C => decrypt_with::<C, _, _>(header, key, container, plaintext))
+}
+
+fn decrypt_with<C: Aead, R: Read, W: Write>(
+    header: &Header,
+    key: &[u8],
+    container: R,
+    mut plaintext: W,
+) -> Result<W> {
+    let cipher = C::new_from_slice(key)?;
+    let mut reader = DecryptReader::new(
+        container,
+        cipher,
+        header.nonce_prefix(),
+        header.chunk_size() as usize,
+    )?
+    .with_associated_data(&header.to_bytes());
+    io::copy(&mut reader, &mut plaintext)?;
+    Ok(plaintext)
+}
+
+/// Encrypt `plaintext` into a container held in memory.
+pub fn seal(header: &Header, key: &[u8], plaintext: &[u8]) -> Result<Vec<u8>> {
+    encrypt(header, key, plaintext, Vec::new())
+}
+
+/// Parse and decrypt a container held in memory.
+pub fn open(key: &[u8], mut container: &[u8]) -> Result<(Header, Vec<u8>)> {
+    let header = Header::read_from(&mut container)?;
+    let plaintext = decrypt(&header, key, container, Vec::new())?;
+    Ok((header, plaintext))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: [u8; 20] = [0x42; 20];
+
+    fn header(algorithm: Algorithm) -> Header {
+        Header::new(algorithm, &[0x11; 11], 64).unwrap()
+    }
+
+    fn key(algorithm: Algorithm) -> &'static [u8] {
+        &KEY[..algorithm.key_size()]
+    }
+
+    #[test]
+    fn round_trip() {
+        let plaintext: Vec<u8> = (0..=255).collect();
+        for algorithm in Algorithm::ALL {
+            for len in [0, 1, 64, 65, 256] {
+                let container =
+                    seal(&header(algorithm), key(algorithm), &plaintext[..len]).unwrap();
+                let segments = len.div_ceil(64).max(1);
+                assert_eq!(
+                    container.len(),
+                    header(algorithm).to_bytes().len() + len + segments * algorithm.tag_size()
+                );
+
+                let (parsed, decrypted) = open(key(algorithm), &container).unwrap();
+                assert_eq!(parsed, header(algorithm));
+                assert_eq!(decrypted, &plaintext[..len], "{}", algorithm.name());
+            }
+        }
+    }
+
+    #[test]
+    fn wrong_key() {
+        let container = seal(&header(Algorithm::Ascon128), &[1; 16], b"secret").unwrap();
+        assert!(matches!(
+            open(&[2; 16], &container),
+            Err(Error::Cipher(lwc_core::Error::AuthenticationFailed))
+        ));
+        assert!(matches!(
+            open(&[1; 20], &container),
+            Err(Error::Cipher(lwc_core::Error::InvalidKeyLength))
+        ));
+        assert!(matches!(
+            seal(&header(Algorithm::Ascon128), &[1; 15], b"secret"),
+            Err(Error::Cipher(lwc_core::Error::InvalidKeyLength))
+        ));
+    }
+}
@@ -0,0 +1,139 @@
+//! Golden, corruption and truncation tests of the container format.
+//!
+//! `golden.txt` freezes version 1 of the format: every container in it must keep decrypting, and
+//! encrypting its fields must keep producing it byte for byte.
+
+use ascon::AsconHead;
+use lwc_container::{open, seal, Algorithm, Error, Header};
+use lwc_core::{kat, Aead};
+
+const KEY: [u8; 16] = [0x42; 16];
+
+fn container() -> (Header, Vec<u8>) {
+    let header = Header::new(Algorithm::Xoodyak, &[0x24; 11], 16)
+        .unwrap()
+        .with_key_id(b"key-1")
+        .unwrap();
+    let plaintext: Vec<u8> = (0..40).collect();
+    let container = seal(&header, &KEY, &plaintext).unwrap();
+    (header, container)
+}
+
+#[test]
+fn golden() {
+    let vectors = kat::parse(include_str!("data/golden.txt"));
+    assert_eq!(vectors.len(), 2 * Algorithm::ALL.len());
+
+    for vector in vectors {
+        let algorithm = Algorithm::from_name(vector.get("Algorithm").unwrap()).unwrap();
+        let chunk_size = vector.get("ChunkSize").unwrap().parse().unwrap();
+        let mut header = Header::new(algorithm, &vector.bytes("Nonce"), chunk_size).unwrap();
+        let key_id = vector.bytes("KeyId");
+        if !key_id.is_empty() {
+            header = header.with_key_id(&key_id).unwrap();
+        }
+        let key = vector.bytes("Key");
+        let plaintext = vector.bytes("PT");
+        let container = vector.bytes("Container");
+
+        assert_eq!(
+            seal(&header, &key, &plaintext).unwrap(),
+            container,
+            "Count = {}",
+            vector.count()
+        );
+        let (parsed, decrypted) = open(&key, &container).unwrap();
+        assert_eq!(parsed, header, "Count = {}", vector.count());
+        assert_eq!(decrypted, plaintext, "Count = {}", vector.count());
+    }
+}
+
+#[test]
+fn layout() {
+    // Decrypt a golden container by hand, following the layout documented on `Header`.
+    let vector = &kat::parse(include_str!("data/golden.txt"))[3];
+    assert_eq!(vector.get("Algorithm"), Some("ascon128"));
+    let container = vector.bytes("Container");
+    let cipher = AsconHead::new_from_slice(&vector.bytes("Key")).unwrap();
+
+    let header_size = 11 + 11 + 1 + 5;
+    let (header, mut body) = container.split_at(header_size);
+    assert_eq!(&header[..6], b"LWC\0\x01\x02");
+    assert_eq!(header[6..10], 16u32.to_be_bytes());
+
+    let mut plaintext = Vec::new();
+    let mut counter = 0u32;
+    while !body.is_empty() {
+        let segment_size = body.len().min(16 + AsconHead::TAG_SIZE);
+        let last = segment_size == body.len();
+
+        let mut nonce = vector.bytes("Nonce");
+        nonce.extend_from_slice(&counter.to_be_bytes());
+        nonce.push(last as u8);
+        let mut segment = body[..segment_size].to_vec();
+        cipher
+            .decrypt_inplace(&nonce, header, &mut segment)
+            .unwrap();
+
+        plaintext.extend_from_slice(&segment);
+        body = &body[segment_size..];
+        counter += 1;
+    }
+    assert_eq!(counter, 3);
+    assert_eq!(plaintext, vector.bytes("PT"));
+}
+
+#[test]
+fn truncated() {
+    let (_, container) = container();
+    for len in 0..container.len() {
+        let error = open(&KEY, &container[..len]).unwrap_err();
+        assert!(
+            matches!(
+                error,
+                Error::TruncatedHeader
+                    | Error::Cipher(
+                        lwc_core::Error::AuthenticationFailed | lwc_core::Error::BufferTooSmall
+                    )
+            ),
+            "{len} bytes: {error}"
+        );
+    }
+}
+
+#[test]
+fn corrupted() {
+    let (header, container) = container();
+
+    // Every byte matters, in the header as well as in the segments.
+    for position in 0..container.len() {
+        let mut corrupted = container.clone();
+        corrupted[position] ^= 0x40;
+        assert!(open(&KEY, &corrupted).is_err(), "byte {position}");
+    }
+
+    let mut extended = container.clone();
+    extended.push(0);
+    assert!(open(&KEY, &extended).is_err());
+
+    // Swapping the first two segments.
+    let start = header.to_bytes().len();
+    let segment_size = 16 + Algorithm::Xoodyak.tag_size();
+    let mut reordered = container.clone();
+    reordered[start..start + 2 * segment_size].rotate_left(segment_size);
+    assert!(matches!(
+        open(&KEY, &reordered),
+        Err(Error::Cipher(lwc_core::Error::AuthenticationFailed))
+    ));
+}
+
+#[test]
+fn key_id_lookup() {
+    let (_, container) = container();
+    let mut reader = &container[..];
+    let header = Header::read_from(&mut reader).unwrap();
+    assert_eq!(header.key_id(), Some(&b"key-1"[..]));
+
+    let plaintext = lwc_container::decrypt(&header, &KEY, reader, Vec::new()).unwrap();
+    assert_eq!(plaintext, (0..40).collect::<Vec<u8>>());
+}
@@ -0,0 +1,72 @@
+use alloc::vec::Vec;
+
+use crate::{Error, Result};
+
+/// Authenticated encryption with associated data.
+///
+/// Implemented by every cipher of the workspace so that callers can be generic over the algorithm
+/// and swap ciphers by changing a type parameter.
+pub trait Aead {
+    /// Length of the key in bytes.
+    const KEY_SIZE: usize;
+    /// Length of the nonce in bytes.
+    const NONCE_SIZE: usize;
+    /// Length of the authentication tag in bytes.
+    const TAG_SIZE: usize;
+
+    /// Authentication tag returned by the detached encryption.
+    type Tag: AsRef<[u8]>;
+
+    /// Create a new instance from a key slice, rejecting keys that are not [`Self::KEY_SIZE`] bytes
+    /// long.
+    fn new_from_slice(key: &[u8]) -> Result<Self>
+    where
+        Self: Sized;
+
+    /// Encrypt `buffer` in place and return the authentication tag.
+    fn encrypt_inplace_detached(
+        &self,
+        nonce: &[u8],
+        associated_data: &[u8],
+        buffer: &mut [u8],
+    ) -> Result<Self::Tag>;
+
+    /// Decrypt `buffer` in place and check it against `tag`.
+    fn decrypt_inplace_detached(
+        &self,
+        nonce: &[u8],
+        associated_data: &[u8],
+        buffer: &mut [u8],
+        tag: &[u8],
+    ) -> Result<()>;
+
+    /// Encrypt `buffer` in place and append the authentication tag to it.
+    fn encrypt_inplace(
+        &self,
+        nonce: &[u8],
+        associated_data: &[u8],
+        buffer: &mut Vec<u8>,
+    ) -> Result<()> {
+        let tag = self.encrypt_inplace_detached(nonce, associated_data, buffer)?;
+        buffer.extend_from_slice(tag.as_ref());
+        Ok(())
+    }
+
+    /// Decrypt a buffer holding `ciphertext || tag` in place and strip the tag from it.
+    fn decrypt_inplace(
+        &self,
+        nonce: &[u8],
+        associated_data: &[u8],
+        buffer: &mut Vec<u8>,
+    ) -> Result<()> {
+        if buffer.len() < Self::TAG_SIZE {
+            return Err(Error::BufferTooSmall);
+        }
+
+        let tag_position = buffer.len() - Self::TAG_SIZE;
+        let (ciphertext, tag) = buffer.split_at_mut(tag_position);
+        self.decrypt_inplace_detached(nonce, associated_data, ciphertext, tag)?;
+        buffer.truncate(tag_position);
+        Ok(())
+    }
+}
@@ -0,0 +1,115 @@
+use core::hint::black_box;
+
+use crate::{Error, Result};
+
+/// OR together the differences of every pair of bytes.
+///
+/// Every pair is visited whatever its content, so the running time only depends on the number of
+/// pairs.
+#[inline(never)]
+fn accumulate_diff(pairs: impl Iterator<Item = (u8, u8)>) -> u8 {
+    pairs.fold(0, |acc, (x, y)| acc | black_box(x ^ y))
+}
+
+/// Compare two byte slices in constant time.
+///
+/// Only the lengths, which are public, can make the comparison return early.
+pub fn ct_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+
+    let diff = accumulate_diff(a.iter().copied().zip(b.iter().copied()));
+    black_box(diff) == 0
+}
+
+/// Check a received authentication tag against the one computed by the cipher.
+pub fn verify_tag(computed: &[u8], received: &[u8]) -> Result<()> {
+    if computed.len() != received.len() {
+        return Err(Error::InvalidTagLength);
+    }
+
+    if ct_eq(computed, received) {
+        Ok(())
+    } else {
+        Err(Error::AuthenticationFailed)
+    }
+}
+
+/// Check a received authentication tag and wipe the decrypted `plaintext` if it does not match.
+///
+/// The tags of the workspace's ciphers depend on the plaintext, so decryption has to happen before
+/// verification; this makes sure unauthenticated plaintext is never handed back to the caller.
+pub fn verify_tag_or_wipe(computed: &[u8], received: &[u8], plaintext: &mut [u8]) -> Result<()> {
+    let result = verify_tag(computed, received);
+    if result.is_err() {
+        plaintext.fill(0);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use core::cell::Cell;
+
+    use super::*;
+
+    #[test]
+    fn equal_and_different() {
+        assert!(ct_eq(&[], &[]));
+        assert!(ct_eq(&[1, 2, 3], &[1, 2, 3]));
+        assert!(!ct_eq(&[1, 2, 3], &[1, 2, 4]));
+        assert!(!ct_eq(&[0x80, 2, 3], &[0, 2, 3]));
+        assert!(!ct_eq(&[1, 2, 3], &[1, 2]));
+    }
+
+    #[test]
+    fn verify_tag_errors() {
+        assert_eq!(verify_tag(&[7; 16], &[7; 16]), Ok(()));
+        assert_eq!(verify_tag(&[7; 16], &[7; 15]), Err(Error::InvalidTagLength));
+        assert_eq!(verify_tag(&[7; 16], &[]), Err(Error::InvalidTagLength));
+        assert_eq!(
+            verify_tag(&[7; 16], &[8; 16]),
+            Err(Error::AuthenticationFailed)
+        );
+    }
+
+    #[test]
+    fn wipe_on_failure() {
+        let mut plaintext = [1u8; 8];
+        assert_eq!(
+            verify_tag_or_wipe(&[7; 16], &[7; 16], &mut plaintext),
+            Ok(())
+        );
+        assert_eq!(plaintext, [1u8; 8]);
+
+        assert_eq!(
+            verify_tag_or_wipe(&[7; 16], &[8; 16], &mut plaintext),
+            Err(Error::AuthenticationFailed)
+        );
+        assert_eq!(plaintext, [0u8; 8]);
+    }
+
+    #[test]
+    fn visits_every_byte() {
+        let reference = [0x5au8; 16];
+        for position in 0..=reference.len() {
+            let mut candidate = reference;
+            if position < candidate.len() {
+                candidate[position] ^= 0xff;
+            }
+
+            let visited = Cell::new(0);
+            let diff = accumulate_diff(
+                reference
+                    .iter()
+                    .copied()
+                    .zip(candidate.iter().copied())
+                    .inspect(|_| visited.set(visited.get() + 1)),
+            );
+
+            assert_eq!(visited.get(), reference.len());
+            assert_eq!(diff == 0, position == reference.len());
+        }
+    }
+}
@@ -0,0 +1,55 @@
+use core::fmt;
+
+/// Errors returned by the ciphers of the workspace.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The key does not have the length required by the cipher.
+    InvalidKeyLength,
+    /// The nonce does not have the length required by the cipher.
+    InvalidNonceLength,
+    /// The authentication tag does not have the length produced by the cipher.
+    InvalidTagLength,
+    /// The input does not have the length of the permutation state.
+    InvalidStateLength,
+    /// The authentication tag does not match the ciphertext and associated data.
+    AuthenticationFailed,
+    /// The buffer is too small to hold the result of the operation.
+    BufferTooSmall,
+    /// The customization string is longer than the function allows.
+    InvalidCustomizationLength,
+    /// The message is longer than the function allows.
+    InvalidMessageLength,
+    /// The key ID does not fit in the block that holds the key.
+    InvalidKeyIdLength,
+    /// A counter would wrap around and reuse a nonce.
+    CounterOverflow,
+    /// Associated data was given after the message had started.
+    AssociatedDataAfterMessage,
+    /// The chunk size of a stream is zero.
+    InvalidChunkSize,
+}
+
+/// Result type used throughout the workspace.
+pub type Result<T> = core::result::Result<T, Error>;
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Error::InvalidKeyLength => "invalid key length",
+            Error::InvalidNonceLength => "invalid nonce length",
+            Error::InvalidTagLength => "invalid tag length",
+            Error::InvalidStateLength => "invalid state length",
+            Error::AuthenticationFailed => "authentication failed",
+            Error::BufferTooSmall => "buffer too small",
+            Error::InvalidCustomizationLength => "invalid customization string length",
+            Error::InvalidMessageLength => "invalid message length",
+            Error::InvalidKeyIdLength => "invalid key ID length",
+            Error::CounterOverflow => "counter overflow",
+            Error::AssociatedDataAfterMessage => "associated data after the message",
+            Error::InvalidChunkSize => "invalid chunk size",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
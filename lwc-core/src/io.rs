@@ -0,0 +1,415 @@
+//! [`std::io`] adapters that encrypt and decrypt with [STREAM](crate::StreamEncryptor).
+//!
+//! The plaintext is cut into chunks of a fixed size, and each chunk is written as one segment of
+//! `chunk_size + C::TAG_SIZE` bytes; only the last segment can be shorter. Neither side holds more
+//! than a segment in memory, so files of any size can go through [`std::io::copy`].
+
+use alloc::vec::Vec;
+use std::io::{self, Read, Write};
+
+use crate::{Aead, Error, StreamDecryptor, StreamEncryptor};
+
+fn invalid_data(error: Error) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, error)
+}
+
+/// Encrypts everything written to it into a STREAM of segments written to `W`.
+///
+/// [`finish`](Self::finish) must be called once all the plaintext is written: it writes the last
+/// segment, without which the reader rejects the stream as truncated. Once the inner writer fails
+/// to take a segment, every later call fails too: the segment may have been written in part, and
+/// the stream cannot be resumed.
+pub struct EncryptWriter<W, C> {
+    inner: W,
+    stream: StreamEncryptor<C>,
+    associated_data: Vec<u8>,
+    buffer: Vec<u8>,
+    chunk_size: usize,
+    poisoned: bool,
+}
+
+impl<W: Write, C: Aead> EncryptWriter<W, C> {
+    /// Encrypt into `inner` in chunks of `chunk_size` bytes, with nonces starting with
+    /// `nonce_prefix` as for [`StreamEncryptor::new`].
+    pub fn new(inner: W, cipher: C, nonce_prefix: &[u8], chunk_size: usize) -> crate::Result<Self> {
+        if chunk_size == 0 {
+            return Err(Error::InvalidChunkSize);
+        }
+
+        Ok(Self {
+            inner,
+            stream: StreamEncryptor::new(cipher, nonce_prefix)?,
+            associated_data: Vec::new(),
+            buffer: Vec::with_capacity(chunk_size + C::TAG_SIZE),
+            chunk_size,
+            poisoned: false,
+        })
+    }
+
+    /// Authenticate `associated_data` with every segment.
+    pub fn with_associated_data(mut self, associated_data: &[u8]) -> Self {
+        self.associated_data = associated_data.to_vec();
+        self
+    }
+
+    /// Write the last segment and return the inner writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.check_poisoned()?;
+        self.stream
+            .encrypt_last(&self.associated_data, &mut self.buffer)
+            .map_err(io::Error::other)?;
+        self.inner.write_all(&self.buffer)?;
+        self.inner.flush()?;
+        Ok(self.inner)
+    }
+
+    fn write_segment(&mut self) -> io::Result<()> {
+        // `buffer` holds the sealed segment until it is written, so a failure in between leaves
+        // nothing that could be resumed.
+        self.poisoned = true;
+        self.stream
+            .encrypt_next(&self.associated_data, &mut self.buffer)
+            .map_err(io::Error::other)?;
+        self.inner.write_all(&self.buffer)?;
+        self.buffer.clear();
+        self.poisoned = false;
+        Ok(())
+    }
+
+    fn check_poisoned(&self) -> io::Result<()> {
+        if self.poisoned {
+            return Err(io::Error::other(
+                "an earlier segment could not be written to the inner writer",
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl<W: Write, C: Aead> Write for EncryptWriter<W, C> {
+    fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+        self.check_poisoned()?;
+        // A full chunk is only encrypted once more data arrives, since the last chunk has to be
+        // encrypted as such by `finish`.
+        if self.buffer.len() == self.chunk_size && !data.is_empty() {
+            self.write_segment()?;
+        }
+
+        let len = data.len().min(self.chunk_size - self.buffer.len());
+        self.buffer.extend_from_slice(&data[..len]);
+        Ok(len)
+    }
+
+    /// Flush the inner writer. Buffered plaintext stays buffered until its chunk is complete.
+    fn flush(&mut self) -> io::Result<()> {
+        self.check_poisoned()?;
+        self.inner.flush()
+    }
+}
+
+/// Decrypts a STREAM of segments read from `R`, handing out each chunk once it is authenticated.
+///
+/// Reads fail with [`io::ErrorKind::InvalidData`] when a segment does not authenticate, including
+/// when the stream is reordered or truncated; every read after that fails too. The end of the
+/// plaintext is only reported once the last segment has been verified.
+pub struct DecryptReader<R, C> {
+    inner: R,
+    stream: Option<StreamDecryptor<C>>,
+    associated_data: Vec<u8>,
+    ciphertext: Vec<u8>,
+    plaintext: Vec<u8>,
+    position: usize,
+    segment_size: usize,
+    failed: bool,
+}
+
+impl<R: Read, C: Aead> DecryptReader<R, C> {
+    /// Decrypt from `inner` a stream encrypted with the same nonce prefix and chunk size.
+    pub fn new(inner: R, cipher: C, nonce_prefix: &[u8], chunk_size: usize) -> crate::Result<Self> {
+        if chunk_size == 0 {
+            return Err(Error::InvalidChunkSize);
+        }
+
+        let segment_size = chunk_size + C::TAG_SIZE;
+        Ok(Self {
+            inner,
+            stream: Some(StreamDecryptor::new(cipher, nonce_prefix)?),
+            associated_data: Vec::new(),
+            ciphertext: Vec::with_capacity(segment_size + 1),
+            plaintext: Vec::with_capacity(segment_size),
+            position: 0,
+            segment_size,
+            failed: false,
+        })
+    }
+
+    /// Check `associated_data` against every segment.
+    pub fn with_associated_data(mut self, associated_data: &[u8]) -> Self {
+        self.associated_data = associated_data.to_vec();
+        self
+    }
+
+    /// Read until a whole segment and one more byte are buffered, or until the end of the input.
+    /// Whether that byte exists tells the last segment apart from the others.
+    fn fill(&mut self) -> io::Result<()> {
+        while self.ciphertext.len() <= self.segment_size {
+            let start = self.ciphertext.len();
+            self.ciphertext.resize(self.segment_size + 1, 0);
+            let len = match self.inner.read(&mut self.ciphertext[start..]) {
+                Ok(0) => {
+                    self.ciphertext.truncate(start);
+                    break;
+                }
+                Ok(len) => len,
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => 0,
+                Err(error) => {
+                    self.ciphertext.truncate(start);
+                    return Err(error);
+                }
+            };
+            self.ciphertext.truncate(start + len);
+        }
+        Ok(())
+    }
+
+    fn next_segment(&mut self) -> io::Result<()> {
+        self.fill()?;
+        let Some(mut stream) = self.stream.take() else {
+            return Ok(());
+        };
+
+        self.plaintext.clear();
+        self.position = 0;
+        let result = if self.ciphertext.len() > self.segment_size {
+            self.plaintext
+                .extend_from_slice(&self.ciphertext[..self.segment_size]);
+            self.ciphertext.drain(..self.segment_size);
+            let result = stream.decrypt_next(&self.associated_data, &mut self.plaintext);
+            self.stream = Some(stream);
+            result
+        } else {
+            self.plaintext.append(&mut self.ciphertext);
+            stream.decrypt_last(&self.associated_data, &mut self.plaintext)
+        };
+
+        result.map_err(|error| {
+            self.plaintext.clear();
+            self.failed = true;
+            invalid_data(error)
+        })
+    }
+}
+
+impl<R: Read, C: Aead> Read for DecryptReader<R, C> {
+    fn read(&mut self, out: &mut [u8]) -> io::Result<usize> {
+        if out.is_empty() {
+            return Ok(0);
+        }
+
+        while self.position == self.plaintext.len() {
+            if self.failed {
+                return Err(invalid_data(Error::AuthenticationFailed));
+            }
+            if self.stream.is_none() {
+                return Ok(0);
+            }
+            self.next_segment()?;
+        }
+
+        let len = out.len().min(self.plaintext.len() - self.position);
+        out[..len].copy_from_slice(&self.plaintext[self.position..self.position + len]);
+        self.position += len;
+        Ok(len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::toy::Toy;
+
+    const PREFIX: [u8; 3] = [0xa0, 0xa1, 0xa2];
+
+    fn encrypt(plaintext: &[u8], chunk_size: usize) -> Vec<u8> {
+        let mut writer =
+            EncryptWriter::new(Vec::new(), Toy { key: 0x5c }, &PREFIX, chunk_size).unwrap();
+        // Uneven writes must not change where the segments fall.
+        for piece in plaintext.chunks(3) {
+            writer.write_all(piece).unwrap();
+        }
+        writer.finish().unwrap()
+    }
+
+    fn decrypt(ciphertext: &[u8], chunk_size: usize) -> io::Result<Vec<u8>> {
+        let mut reader =
+            DecryptReader::new(ciphertext, Toy { key: 0x5c }, &PREFIX, chunk_size).unwrap();
+        let mut plaintext = Vec::new();
+        reader.read_to_end(&mut plaintext)?;
+        Ok(plaintext)
+    }
+
+    #[test]
+    fn round_trip() {
+        let plaintext: Vec<u8> = (0..100).collect();
+        for len in [0, 1, 15, 16, 17, 32, 100] {
+            let ciphertext = encrypt(&plaintext[..len], 16);
+            let segments = len.div_ceil(16).max(1);
+            assert_eq!(ciphertext.len(), len + segments * Toy::TAG_SIZE);
+            assert_eq!(decrypt(&ciphertext, 16).unwrap(), &plaintext[..len]);
+        }
+    }
+
+    #[test]
+    fn copy() {
+        let plaintext = [0x42u8; 1000];
+        let mut writer = EncryptWriter::new(Vec::new(), Toy { key: 1 }, &PREFIX, 64)
+            .unwrap()
+            .with_associated_data(b"header");
+        io::copy(&mut &plaintext[..], &mut writer).unwrap();
+        let ciphertext = writer.finish().unwrap();
+
+        let mut reader = DecryptReader::new(&ciphertext[..], Toy { key: 1 }, &PREFIX, 64)
+            .unwrap()
+            .with_associated_data(b"header");
+        let mut decrypted = Vec::new();
+        io::copy(&mut reader, &mut decrypted).unwrap();
+        assert_eq!(decrypted, plaintext);
+
+        let mut reader = DecryptReader::new(&ciphertext[..], Toy { key: 1 }, &PREFIX, 64)
+            .unwrap()
+            .with_associated_data(b"other");
+        assert_eq!(
+            reader.read(&mut [0u8; 8]).unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+    }
+
+    #[test]
+    fn truncated() {
+        let ciphertext = encrypt(&[7u8; 40], 16);
+        let segment_size = 16 + Toy::TAG_SIZE;
+
+        // Cut at a segment boundary, inside a segment and inside a tag.
+        for len in [0, segment_size, segment_size + 5, 2 * segment_size - 1] {
+            let error = decrypt(&ciphertext[..len], 16).unwrap_err();
+            assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        }
+    }
+
+    #[test]
+    fn corrupted() {
+        let mut ciphertext = encrypt(&[7u8; 40], 16);
+        ciphertext[30] ^= 1;
+
+        let mut reader =
+            DecryptReader::new(&ciphertext[..], Toy { key: 0x5c }, &PREFIX, 16).unwrap();
+        let mut out = [0u8; 40];
+        assert_eq!(reader.read(&mut out).unwrap(), 16);
+        assert_eq!(out[..16], [7u8; 16]);
+        for _ in 0..2 {
+            let error = reader.read(&mut out).unwrap_err();
+            assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        }
+    }
+
+    #[test]
+    fn chunk_size_mismatch() {
+        let ciphertext = encrypt(&[7u8; 40], 16);
+        assert!(decrypt(&ciphertext, 8).is_err());
+        assert!(decrypt(&ciphertext, 32).is_err());
+    }
+
+    #[test]
+    fn reordered() {
+        let ciphertext = encrypt(&[7u8; 40], 16);
+        let segment_size = 16 + Toy::TAG_SIZE;
+
+        let mut reordered = ciphertext[segment_size..2 * segment_size].to_vec();
+        reordered.extend_from_slice(&ciphertext[..segment_size]);
+        reordered.extend_from_slice(&ciphertext[2 * segment_size..]);
+        let error = decrypt(&reordered, 16).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn read_error() {
+        struct Failing<'a>(&'a [u8], bool);
+
+        impl Read for Failing<'_> {
+            fn read(&mut self, out: &mut [u8]) -> io::Result<usize> {
+                // Fail once after the first segment, then resume.
+                if self.0.len() < 30 && !self.1 {
+                    self.1 = true;
+                    return Err(io::ErrorKind::BrokenPipe.into());
+                }
+                let len = out.len().min(10);
+                self.0.read(&mut out[..len])
+            }
+        }
+
+        let ciphertext = encrypt(&[7u8; 40], 16);
+        let mut reader =
+            DecryptReader::new(Failing(&ciphertext, false), Toy { key: 0x5c }, &PREFIX, 16)
+                .unwrap();
+        let mut out = Vec::new();
+        let error = reader.read_to_end(&mut out).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::BrokenPipe);
+
+        // The stream is not lost, so reading on picks up where it stopped.
+        reader.read_to_end(&mut out).unwrap();
+        assert_eq!(out, [7u8; 40]);
+    }
+
+    #[test]
+    fn write_error() {
+        /// Takes at most `limit` bytes, failing every write after that.
+        #[derive(Debug)]
+        struct Failing {
+            written: Vec<u8>,
+            limit: usize,
+        }
+
+        impl Write for Failing {
+            fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+                if self.written.len() == self.limit {
+                    return Err(io::ErrorKind::WouldBlock.into());
+                }
+                let len = data.len().min(self.limit - self.written.len());
+                self.written.extend_from_slice(&data[..len]);
+                Ok(len)
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        // The first segment is cut short, then the writer would take more.
+        let inner = Failing {
+            written: Vec::new(),
+            limit: 10,
+        };
+        let mut writer = EncryptWriter::new(inner, Toy { key: 0x5c }, &PREFIX, 16).unwrap();
+        writer.write_all(&[7u8; 16]).unwrap();
+        let error = writer.write_all(&[7u8; 16]).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::WouldBlock);
+
+        writer.inner.limit = usize::MAX;
+        for result in [writer.write(&[7u8; 16]).map(drop), writer.flush()] {
+            assert_eq!(result.unwrap_err().kind(), io::ErrorKind::Other);
+        }
+        assert_eq!(writer.finish().unwrap_err().kind(), io::ErrorKind::Other);
+    }
+
+    #[test]
+    fn zero_chunk_size() {
+        assert!(matches!(
+            EncryptWriter::new(Vec::new(), Toy { key: 0x5c }, &PREFIX, 0),
+            Err(Error::InvalidChunkSize)
+        ));
+        assert!(matches!(
+            DecryptReader::new(&[][..], Toy { key: 0x5c }, &PREFIX, 0),
+            Err(Error::InvalidChunkSize)
+        ));
+    }
+}
@@ -0,0 +1,124 @@
+//! Parser for known-answer test files in the format of the NIST lightweight cryptography project.
+//!
+//! A file is a sequence of records separated by blank lines, each record holding one field per
+//! line:
+//!
+//! ```text
+//! Count = 1
+//! Key = 000102030405060708090A0B0C0D0E0F
+//! Nonce = 000102030405060708090A0B0C0D0E0F
+//! PT =
+//! AD =
+//! CT = E355159F292911F794CB1432A0103A8A
+//! ```
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// A single record of a known-answer test file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Vector {
+    fields: Vec<(String, String)>,
+}
+
+impl Vector {
+    /// Raw value of the field `name`, if present.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(field, _)| field == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Number of the record, given by its `Count` field.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the record has no valid `Count` field.
+    pub fn count(&self) -> usize {
+        self.get("Count")
+            .and_then(|count| count.parse().ok())
+            .expect("record without a valid Count field")
+    }
+
+    /// Decode the hexadecimal value of the field `name`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the field is missing or is not valid hexadecimal.
+    pub fn bytes(&self, name: &str) -> Vec<u8> {
+        let value = self
+            .get(name)
+            .unwrap_or_else(|| panic!("record without a {name} field"));
+        decode_hex(value).unwrap_or_else(|| panic!("invalid hexadecimal in {name} field"))
+    }
+}
+
+/// Parse all records of a known-answer test file.
+pub fn parse(input: &str) -> Vec<Vector> {
+    let mut vectors = Vec::new();
+    let mut fields = Vec::new();
+    for line in input.lines().map(str::trim) {
+        if line.is_empty() {
+            if !fields.is_empty() {
+                vectors.push(Vector {
+                    fields: core::mem::take(&mut fields),
+                });
+            }
+            continue;
+        }
+
+        if let Some((name, value)) = line.split_once('=') {
+            fields.push((String::from(name.trim()), String::from(value.trim())));
+        }
+    }
+    if !fields.is_empty() {
+        vectors.push(Vector { fields });
+    }
+    vectors
+}
+
+fn decode_hex(value: &str) -> Option<Vec<u8>> {
+    if !value.len().is_multiple_of(2) {
+        return None;
+    }
+
+    value
+        .as_bytes()
+        .chunks_exact(2)
+        .map(|pair| {
+            let high = (pair[0] as char).to_digit(16)?;
+            let low = (pair[1] as char).to_digit(16)?;
+            Some((high << 4 | low) as u8)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "Count = 1\nKey = 000102\nPT = \n\nCount = 2\nKey = 0A0b\r\nPT = FF\n";
+
+    #[test]
+    fn parse_records() {
+        let vectors = parse(INPUT);
+        assert_eq!(vectors.len(), 2);
+
+        assert_eq!(vectors[0].count(), 1);
+        assert_eq!(vectors[0].bytes("Key"), [0x00, 0x01, 0x02]);
+        assert_eq!(vectors[0].bytes("PT"), []);
+
+        assert_eq!(vectors[1].count(), 2);
+        assert_eq!(vectors[1].bytes("Key"), [0x0a, 0x0b]);
+        assert_eq!(vectors[1].bytes("PT"), [0xff]);
+        assert_eq!(vectors[1].get("AD"), None);
+    }
+
+    #[test]
+    fn invalid_hex() {
+        assert_eq!(decode_hex("0"), None);
+        assert_eq!(decode_hex("0g"), None);
+        assert_eq!(decode_hex(""), Some(Vec::new()));
+    }
+}
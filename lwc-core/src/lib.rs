@@ -0,0 +1,24 @@
+#![no_std]
+#![forbid(unsafe_code)]
+
+pub use aead::Aead;
+pub use ct::{ct_eq, verify_tag, verify_tag_or_wipe};
+pub use error::{Error, Result};
+#[cfg(feature = "std")]
+pub use io::{DecryptReader, EncryptWriter};
+pub use stream::{StreamDecryptor, StreamEncryptor, STREAM_NONCE_OVERHEAD};
+
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+mod aead;
+mod ct;
+mod error;
+#[cfg(feature = "std")]
+mod io;
+#[cfg(feature = "kat")]
+pub mod kat;
+mod stream;
+#[cfg(test)]
+mod toy;
@@ -0,0 +1,242 @@
+//! STREAM, the online authenticated encryption of a message split into segments.
+//!
+//! Each segment is encrypted on its own with the nonce `prefix || counter || last`, where the
+//! counter is the 32-bit big-endian index of the segment and `last` is `1` for the final segment
+//! and `0` otherwise, as in the `StreamBE32` construction of the RustCrypto `aead` crate. Every
+//! segment can then be authenticated as soon as it arrives, while reordered, dropped or truncated
+//! segments still fail to decrypt.
+
+use alloc::vec::Vec;
+
+use crate::{Aead, Error, Result};
+
+/// Bytes of the nonce taken by the counter and the last-segment flag.
+pub const STREAM_NONCE_OVERHEAD: usize = 5;
+
+/// Nonces and position shared by [`StreamEncryptor`] and [`StreamDecryptor`].
+struct Segments {
+    nonce: Vec<u8>,
+    counter: u32,
+}
+
+impl Segments {
+    fn new<A: Aead>(nonce_prefix: &[u8]) -> Result<Self> {
+        if A::NONCE_SIZE < STREAM_NONCE_OVERHEAD
+            || nonce_prefix.len() != A::NONCE_SIZE - STREAM_NONCE_OVERHEAD
+        {
+            return Err(Error::InvalidNonceLength);
+        }
+
+        let mut nonce = Vec::with_capacity(A::NONCE_SIZE);
+        nonce.extend_from_slice(nonce_prefix);
+        nonce.resize(A::NONCE_SIZE, 0);
+        Ok(Self { nonce, counter: 0 })
+    }
+
+    /// Nonce of the current segment.
+    fn nonce(&mut self, last: bool) -> &[u8] {
+        let flag = self.nonce.len() - 1;
+        self.nonce[flag - 4..flag].copy_from_slice(&self.counter.to_be_bytes());
+        self.nonce[flag] = last as u8;
+        &self.nonce
+    }
+
+    /// Nonce of the current segment when it is not the last one.
+    ///
+    /// The last counter value is kept for the last segment, so that no counter is ever reused.
+    fn next_nonce(&mut self) -> Result<&[u8]> {
+        if self.counter == u32::MAX {
+            return Err(Error::CounterOverflow);
+        }
+        Ok(self.nonce(false))
+    }
+}
+
+/// Encrypts a message one segment at a time.
+pub struct StreamEncryptor<A> {
+    cipher: A,
+    segments: Segments,
+}
+
+impl<A: Aead> StreamEncryptor<A> {
+    /// Start a stream whose nonces begin with `nonce_prefix`, which must be
+    /// `A::NONCE_SIZE - STREAM_NONCE_OVERHEAD` bytes long and never reused with the key.
+    pub fn new(cipher: A, nonce_prefix: &[u8]) -> Result<Self> {
+        Ok(Self {
+            cipher,
+            segments: Segments::new::<A>(nonce_prefix)?,
+        })
+    }
+
+    /// Encrypt a segment that is not the last one in place and append its tag.
+    pub fn encrypt_next(&mut self, associated_data: &[u8], buffer: &mut Vec<u8>) -> Result<()> {
+        let nonce = self.segments.next_nonce()?;
+        self.cipher
+            .encrypt_inplace(nonce, associated_data, buffer)?;
+        self.segments.counter += 1;
+        Ok(())
+    }
+
+    /// Encrypt the last segment in place and append its tag, which ends the stream.
+    pub fn encrypt_last(mut self, associated_data: &[u8], buffer: &mut Vec<u8>) -> Result<()> {
+        let nonce = self.segments.nonce(true);
+        self.cipher.encrypt_inplace(nonce, associated_data, buffer)
+    }
+}
+
+/// Decrypts a message one segment at a time, in the order the segments were encrypted.
+pub struct StreamDecryptor<A> {
+    cipher: A,
+    segments: Segments,
+}
+
+impl<A: Aead> StreamDecryptor<A> {
+    /// Start a stream whose nonces begin with `nonce_prefix`.
+    pub fn new(cipher: A, nonce_prefix: &[u8]) -> Result<Self> {
+        Ok(Self {
+            cipher,
+            segments: Segments::new::<A>(nonce_prefix)?,
+        })
+    }
+
+    /// Decrypt a segment that is not the last one in place and strip its tag.
+    ///
+    /// A segment that fails to authenticate leaves the counter where it was, but the ciphers of
+    /// the workspace wipe `buffer` when they reject it, so the segment has to be fetched again
+    /// before it can be retried.
+    pub fn decrypt_next(&mut self, associated_data: &[u8], buffer: &mut Vec<u8>) -> Result<()> {
+        let nonce = self.segments.next_nonce()?;
+        self.cipher
+            .decrypt_inplace(nonce, associated_data, buffer)?;
+        self.segments.counter += 1;
+        Ok(())
+    }
+
+    /// Decrypt the last segment in place and strip its tag.
+    ///
+    /// This fails for any segment that was not encrypted as the last one, which is how a
+    /// truncated stream is detected: the receiver has to see the end of the stream succeed.
+    pub fn decrypt_last(mut self, associated_data: &[u8], buffer: &mut Vec<u8>) -> Result<()> {
+        let nonce = self.segments.nonce(true);
+        self.cipher.decrypt_inplace(nonce, associated_data, buffer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::toy::Toy;
+
+    const PREFIX: [u8; 3] = [0xa0, 0xa1, 0xa2];
+
+    fn encrypt(segments: &[&[u8]]) -> Vec<Vec<u8>> {
+        let mut encryptor = StreamEncryptor::new(Toy { key: 0x5c }, &PREFIX).unwrap();
+        let (last, rest) = segments.split_last().unwrap();
+        let mut encrypted: Vec<Vec<u8>> = Vec::new();
+        for segment in rest {
+            let mut buffer = segment.to_vec();
+            encryptor.encrypt_next(b"ad", &mut buffer).unwrap();
+            encrypted.push(buffer);
+        }
+        let mut buffer = last.to_vec();
+        encryptor.encrypt_last(b"ad", &mut buffer).unwrap();
+        encrypted.push(buffer);
+        encrypted
+    }
+
+    fn decryptor() -> StreamDecryptor<Toy> {
+        StreamDecryptor::new(Toy { key: 0x5c }, &PREFIX).unwrap()
+    }
+
+    #[test]
+    fn nonces() {
+        let mut segments = Segments::new::<Toy>(&PREFIX).unwrap();
+        assert_eq!(
+            segments.next_nonce(),
+            Ok(&[0xa0, 0xa1, 0xa2, 0, 0, 0, 0, 0][..])
+        );
+        segments.counter = 0x0102_0304;
+        assert_eq!(segments.nonce(true), [0xa0, 0xa1, 0xa2, 1, 2, 3, 4, 1]);
+
+        segments.counter = u32::MAX;
+        assert_eq!(segments.next_nonce(), Err(Error::CounterOverflow));
+    }
+
+    #[test]
+    fn in_order() {
+        let segments: [&[u8]; 3] = [b"first", b"second", b""];
+        let mut encrypted = encrypt(&segments);
+        assert!(encrypted
+            .iter()
+            .all(|segment| segment.len() >= Toy::TAG_SIZE));
+
+        let mut decryptor = decryptor();
+        let (last, rest) = encrypted.split_last_mut().unwrap();
+        for (segment, expected) in rest.iter_mut().zip(segments) {
+            decryptor.decrypt_next(b"ad", segment).unwrap();
+            assert_eq!(segment, expected);
+        }
+        decryptor.decrypt_last(b"ad", last).unwrap();
+        assert!(last.is_empty());
+    }
+
+    #[test]
+    fn reordered() {
+        let encrypted = encrypt(&[b"first", b"second", b"third"]);
+        let mut decryptor = decryptor();
+
+        let mut buffer = encrypted[1].clone();
+        assert_eq!(
+            decryptor.decrypt_next(b"ad", &mut buffer),
+            Err(Error::AuthenticationFailed)
+        );
+
+        // The failure left the counter where it was.
+        let mut buffer = encrypted[0].clone();
+        assert_eq!(decryptor.decrypt_next(b"ad", &mut buffer), Ok(()));
+    }
+
+    #[test]
+    fn truncated() {
+        let encrypted = encrypt(&[b"first", b"second", b"third"]);
+        let mut decryptor = decryptor();
+
+        let mut buffer = encrypted[0].clone();
+        decryptor.decrypt_next(b"ad", &mut buffer).unwrap();
+        let mut buffer = encrypted[1].clone();
+        assert_eq!(
+            decryptor.decrypt_last(b"ad", &mut buffer),
+            Err(Error::AuthenticationFailed)
+        );
+    }
+
+    #[test]
+    fn last_segment_is_final() {
+        // The last segment cannot pass for an ordinary one, nor the other way round.
+        let encrypted = encrypt(&[b"only"]);
+        let mut buffer = encrypted[0].clone();
+        assert_eq!(
+            decryptor().decrypt_next(b"ad", &mut buffer),
+            Err(Error::AuthenticationFailed)
+        );
+
+        let encrypted = encrypt(&[b"first", b""]);
+        let mut buffer = encrypted[0].clone();
+        assert_eq!(
+            decryptor().decrypt_last(b"ad", &mut buffer),
+            Err(Error::AuthenticationFailed)
+        );
+    }
+
+    #[test]
+    fn nonce_prefix_length() {
+        assert_eq!(
+            StreamEncryptor::new(Toy { key: 0 }, &[0u8; 4]).err(),
+            Some(Error::InvalidNonceLength)
+        );
+        assert_eq!(
+            StreamDecryptor::new(Toy { key: 0 }, &[0u8; 2]).err(),
+            Some(Error::InvalidNonceLength)
+        );
+    }
+}
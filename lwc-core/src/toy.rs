@@ -0,0 +1,57 @@
+//! Toy cipher for the tests of the constructions built on [`Aead`].
+
+use crate::{Aead, Error, Result};
+
+/// Toy cipher whose tag depends on the key, nonce, associated data and ciphertext.
+pub(crate) struct Toy {
+    pub(crate) key: u8,
+}
+
+impl Toy {
+    fn tag(&self, nonce: &[u8], associated_data: &[u8], ciphertext: &[u8]) -> [u8; 4] {
+        let mut hash = 0x811c_9dc5_u32 ^ self.key as u32;
+        for (domain, input) in [nonce, associated_data, ciphertext].iter().enumerate() {
+            for &byte in input.iter().chain(&[domain as u8, input.len() as u8]) {
+                hash = (hash ^ byte as u32).wrapping_mul(0x0100_0193);
+            }
+        }
+        hash.to_be_bytes()
+    }
+}
+
+impl Aead for Toy {
+    const KEY_SIZE: usize = 1;
+    const NONCE_SIZE: usize = 8;
+    const TAG_SIZE: usize = 4;
+
+    type Tag = [u8; 4];
+
+    fn new_from_slice(key: &[u8]) -> Result<Self> {
+        match key {
+            [key] => Ok(Self { key: *key }),
+            _ => Err(Error::InvalidKeyLength),
+        }
+    }
+
+    fn encrypt_inplace_detached(
+        &self,
+        nonce: &[u8],
+        associated_data: &[u8],
+        buffer: &mut [u8],
+    ) -> Result<Self::Tag> {
+        buffer.iter_mut().for_each(|byte| *byte ^= self.key);
+        Ok(self.tag(nonce, associated_data, buffer))
+    }
+
+    fn decrypt_inplace_detached(
+        &self,
+        nonce: &[u8],
+        associated_data: &[u8],
+        buffer: &mut [u8],
+        tag: &[u8],
+    ) -> Result<()> {
+        crate::verify_tag(&self.tag(nonce, associated_data, buffer), tag)?;
+        buffer.iter_mut().for_each(|byte| *byte ^= self.key);
+        Ok(())
+    }
+}
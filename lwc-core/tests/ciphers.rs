@@ -0,0 +1,164 @@
+//! STREAM and its I/O adapters over every cipher of the workspace, through the [`Aead`] trait.
+//!
+//! The unit tests of the constructions run on a toy cipher; these check that the real ciphers
+//! behave the same way, including that they wipe the ciphertext of a segment they reject.
+
+#[cfg(feature = "std")]
+use std::io::{self, Read};
+
+use acorn::AcornHead;
+use ascon::{Ascon128aHead, Ascon80pqHead, AsconAead128Head, AsconHead};
+use lwc_core::{Aead, Error, StreamDecryptor, StreamEncryptor, STREAM_NONCE_OVERHEAD};
+#[cfg(feature = "std")]
+use lwc_core::{DecryptReader, EncryptWriter};
+use xoodyak::XoodyakAead;
+
+const KEY: [u8; 20] = [0x42; 20];
+const NONCE: [u8; 16] = [0x24; 16];
+
+fn cipher<A: Aead>() -> A {
+    A::new_from_slice(&KEY[..A::KEY_SIZE]).unwrap()
+}
+
+fn prefix<A: Aead>() -> &'static [u8] {
+    &NONCE[..A::NONCE_SIZE - STREAM_NONCE_OVERHEAD]
+}
+
+fn message() -> Vec<u8> {
+    (0..100).collect()
+}
+
+fn encrypt_segments<A: Aead>(message: &[u8]) -> Vec<Vec<u8>> {
+    let chunks: Vec<&[u8]> = message.chunks(32).collect();
+    let (last, rest) = chunks.split_last().unwrap();
+
+    let mut encryptor = StreamEncryptor::new(cipher::<A>(), prefix::<A>()).unwrap();
+    let mut segments = Vec::new();
+    for chunk in rest {
+        let mut buffer = chunk.to_vec();
+        encryptor.encrypt_next(b"header", &mut buffer).unwrap();
+        segments.push(buffer);
+    }
+    let mut buffer = last.to_vec();
+    encryptor.encrypt_last(b"header", &mut buffer).unwrap();
+    segments.push(buffer);
+    segments
+}
+
+fn stream<A: Aead>() {
+    let message = message();
+    let mut segments = encrypt_segments::<A>(&message);
+
+    let mut decryptor = StreamDecryptor::new(cipher::<A>(), prefix::<A>()).unwrap();
+    let (last, rest) = segments.split_last_mut().unwrap();
+    let mut decrypted = Vec::new();
+    for segment in rest {
+        decryptor.decrypt_next(b"header", segment).unwrap();
+        decrypted.extend_from_slice(segment);
+    }
+    decryptor.decrypt_last(b"header", last).unwrap();
+    decrypted.extend_from_slice(last);
+    assert_eq!(decrypted, message);
+}
+
+fn stream_retry<A: Aead>() {
+    let segments = encrypt_segments::<A>(&message());
+    let mut decryptor = StreamDecryptor::new(cipher::<A>(), prefix::<A>()).unwrap();
+
+    let mut buffer = segments[0].clone();
+    buffer[0] ^= 1;
+    assert_eq!(
+        decryptor.decrypt_next(b"header", &mut buffer),
+        Err(Error::AuthenticationFailed)
+    );
+    assert!(buffer[..32].iter().all(|&byte| byte == 0));
+
+    // The rejected segment can be decrypted once it has been fetched again.
+    let mut buffer = segments[0].clone();
+    assert_eq!(decryptor.decrypt_next(b"header", &mut buffer), Ok(()));
+    assert_eq!(buffer, &message()[..32]);
+}
+
+fn stream_truncated<A: Aead>() {
+    let segments = encrypt_segments::<A>(&message());
+    let mut decryptor = StreamDecryptor::new(cipher::<A>(), prefix::<A>()).unwrap();
+
+    let mut buffer = segments[0].clone();
+    decryptor.decrypt_next(b"header", &mut buffer).unwrap();
+    let mut buffer = segments[1].clone();
+    assert_eq!(
+        decryptor.decrypt_last(b"header", &mut buffer),
+        Err(Error::AuthenticationFailed)
+    );
+}
+
+#[cfg(feature = "std")]
+fn io_round_trip<A: Aead>() {
+    let message = message();
+    let mut writer = EncryptWriter::new(Vec::new(), cipher::<A>(), prefix::<A>(), 32)
+        .unwrap()
+        .with_associated_data(b"header");
+    io::copy(&mut &message[..], &mut writer).unwrap();
+    let ciphertext = writer.finish().unwrap();
+    assert_eq!(ciphertext, encrypt_segments::<A>(&message).concat());
+
+    let mut reader = DecryptReader::new(&ciphertext[..], cipher::<A>(), prefix::<A>(), 32)
+        .unwrap()
+        .with_associated_data(b"header");
+    let mut decrypted = Vec::new();
+    io::copy(&mut reader, &mut decrypted).unwrap();
+    assert_eq!(decrypted, message);
+}
+
+#[cfg(feature = "std")]
+fn io_truncated<A: Aead>() {
+    let ciphertext = encrypt_segments::<A>(&message()).concat();
+    let segment_size = 32 + A::TAG_SIZE;
+
+    // Cut at a segment boundary, inside a segment and inside the last tag.
+    for len in [0, segment_size, segment_size + 5, ciphertext.len() - 1] {
+        let mut reader = DecryptReader::new(&ciphertext[..len], cipher::<A>(), prefix::<A>(), 32)
+            .unwrap()
+            .with_associated_data(b"header");
+        let error = reader.read_to_end(&mut Vec::new()).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData, "{len} bytes");
+    }
+}
+
+macro_rules! for_each_cipher {
+    ($test:ident) => {
+        $test::<AcornHead>();
+        $test::<AsconHead>();
+        $test::<Ascon128aHead>();
+        $test::<Ascon80pqHead>();
+        $test::<AsconAead128Head>();
+        $test::<XoodyakAead>();
+    };
+}
+
+#[test]
+fn round_trip() {
+    for_each_cipher!(stream);
+}
+
+#[test]
+fn retry() {
+    for_each_cipher!(stream_retry);
+}
+
+#[test]
+fn truncated() {
+    for_each_cipher!(stream_truncated);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn reader_and_writer() {
+    for_each_cipher!(io_round_trip);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn truncated_reader() {
+    for_each_cipher!(io_truncated);
+}
@@ -0,0 +1,99 @@
+//! Statistical timing test for the tag comparison, in the spirit of dudect.
+//!
+//! Two classes of inputs are compared against a fixed reference: one equal to it and one that
+//! differs in the very first byte, which is the best case for an early-exit comparison.
+//! Measurements of both classes are interleaved at random and Welch's t-test tells whether their
+//! timing distributions differ. The control test checks that the harness does detect the leak of a
+//! short-circuiting comparison.
+//!
+//! Timing is too noisy in debug builds and on loaded machines for these to be part of the default
+//! run:
+//!
+//! ```text
+//! cargo test --release -p lwc-core --test constant_time -- --ignored
+//! ```
+
+use std::hint::black_box;
+use std::time::Instant;
+
+use lwc_core::ct_eq;
+
+const INPUT_LEN: usize = 16 * 1024;
+const SAMPLES: usize = 10_000;
+const BATCH: usize = 4;
+const THRESHOLD: f64 = 10.0;
+
+struct XorShift(u64);
+
+impl XorShift {
+    fn next_bool(&mut self) -> bool {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0 & 1 == 1
+    }
+}
+
+#[derive(Default)]
+struct Welch {
+    n: [f64; 2],
+    mean: [f64; 2],
+    m2: [f64; 2],
+}
+
+impl Welch {
+    fn push(&mut self, class: usize, x: f64) {
+        self.n[class] += 1.0;
+        let delta = x - self.mean[class];
+        self.mean[class] += delta / self.n[class];
+        self.m2[class] += delta * (x - self.mean[class]);
+    }
+
+    fn t(&self) -> f64 {
+        let var0 = self.m2[0] / (self.n[0] - 1.0);
+        let var1 = self.m2[1] / (self.n[1] - 1.0);
+        (self.mean[0] - self.mean[1]) / (var0 / self.n[0] + var1 / self.n[1]).sqrt()
+    }
+}
+
+fn measure(compare: impl Fn(&[u8], &[u8]) -> bool) -> f64 {
+    let reference = vec![0xa5u8; INPUT_LEN];
+    let equal = reference.clone();
+    let mut different = reference.clone();
+    different[0] ^= 0x01;
+
+    let mut rng = XorShift(0x9e37_79b9_7f4a_7c15);
+    let mut welch = Welch::default();
+    for _ in 0..SAMPLES {
+        let class = rng.next_bool() as usize;
+        let candidate = if class == 0 { &equal } else { &different };
+
+        let start = Instant::now();
+        for _ in 0..BATCH {
+            black_box(compare(black_box(&reference), black_box(candidate)));
+        }
+        welch.push(class, start.elapsed().as_nanos() as f64);
+    }
+
+    welch.t()
+}
+
+#[test]
+#[ignore]
+fn ct_eq_is_not_data_dependent() {
+    let t = measure(ct_eq);
+    assert!(
+        t.abs() < THRESHOLD,
+        "timing depends on the data (t = {t:.2})"
+    );
+}
+
+#[test]
+#[ignore]
+fn harness_detects_early_exit() {
+    let t = measure(|a, b| a == b);
+    assert!(
+        t.abs() >= THRESHOLD,
+        "early exit went unnoticed (t = {t:.2})"
+    );
+}
@@ -0,0 +1,158 @@
+use core::fmt;
+
+use crate::xoodyak::{Xoodyak, HASH_RATE};
+
+/// Length of the Xoodyak-Hash digest in bytes.
+pub const HASH_SIZE: usize = 32;
+
+/// Xoodyak in hash mode: absorbs a message given in any number of pieces, then squeezes either
+/// the 32-byte Xoodyak-Hash digest or output of any length.
+#[derive(Clone)]
+pub struct XoodyakHash {
+    xoodyak: Xoodyak,
+    buffer: [u8; HASH_RATE],
+    position: usize,
+    first: bool,
+}
+
+impl XoodyakHash {
+    pub fn new() -> Self {
+        Self {
+            xoodyak: Xoodyak::new_hash(),
+            buffer: [0u8; HASH_RATE],
+            position: 0,
+            first: true,
+        }
+    }
+
+    pub fn update(&mut self, mut data: &[u8]) {
+        while !data.is_empty() {
+            // A full block is only absorbed once more data arrives, since the last block of the
+            // message is absorbed by `finalize_xof` whatever its length.
+            if self.position == HASH_RATE {
+                let block = self.buffer;
+                self.xoodyak.absorb_block(&block, self.first);
+                self.first = false;
+                self.position = 0;
+            }
+
+            let len = data.len().min(HASH_RATE - self.position);
+            self.buffer[self.position..self.position + len].copy_from_slice(&data[..len]);
+            self.position += len;
+            data = &data[len..];
+        }
+    }
+
+    pub fn finalize_xof(mut self) -> XoodyakHashReader {
+        let block = self.buffer;
+        self.xoodyak
+            .absorb_block(&block[..self.position], self.first);
+        XoodyakHashReader {
+            xoodyak: self.xoodyak,
+            block: [0u8; HASH_RATE],
+            position: HASH_RATE,
+            first: true,
+        }
+    }
+
+    pub fn finalize(self) -> [u8; HASH_SIZE] {
+        let mut digest = [0u8; HASH_SIZE];
+        self.finalize_xof().squeeze(&mut digest);
+        digest
+    }
+
+    /// Hash `data` in one call.
+    pub fn digest(data: &[u8]) -> [u8; HASH_SIZE] {
+        let mut hasher = Self::new();
+        hasher.update(data);
+        hasher.finalize()
+    }
+}
+
+impl Default for XoodyakHash {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for XoodyakHash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("XoodyakHash").finish_non_exhaustive()
+    }
+}
+
+/// Output of [`XoodyakHash`], read with as many calls to [`squeeze`] as needed.
+///
+/// [`squeeze`]: XoodyakHashReader::squeeze
+#[derive(Clone)]
+pub struct XoodyakHashReader {
+    xoodyak: Xoodyak,
+    block: [u8; HASH_RATE],
+    position: usize,
+    first: bool,
+}
+
+impl XoodyakHashReader {
+    /// Fill `out` with the next bytes of output.
+    pub fn squeeze(&mut self, mut out: &mut [u8]) {
+        while !out.is_empty() {
+            if self.position == HASH_RATE {
+                self.xoodyak.squeeze_block(&mut self.block, self.first);
+                self.first = false;
+                self.position = 0;
+            }
+
+            let len = out.len().min(HASH_RATE - self.position);
+            out[..len].copy_from_slice(&self.block[self.position..self.position + len]);
+            self.position += len;
+            out = &mut out[len..];
+        }
+    }
+}
+
+impl fmt::Debug for XoodyakHashReader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("XoodyakHashReader").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn incremental_update() {
+        let data: [u8; 50] = core::array::from_fn(|i| i as u8);
+        let expected = XoodyakHash::digest(&data);
+
+        for split in 0..data.len() {
+            let mut hasher = XoodyakHash::new();
+            for chunk in [&data[..split], &[], &data[split..]] {
+                hasher.update(chunk);
+            }
+            assert_eq!(hasher.finalize(), expected);
+        }
+    }
+
+    #[test]
+    fn matches_cyclist() {
+        // Buffering must not change where the block boundaries fall, including for messages
+        // filling their last block exactly.
+        for len in [0, 1, HASH_RATE, 2 * HASH_RATE, 2 * HASH_RATE + 3] {
+            let data = [0x5a; 2 * HASH_RATE + 3];
+            let mut xoodyak = Xoodyak::new_hash();
+            xoodyak.absorb(&data[..len]);
+            let mut expected = [0u8; 50];
+            xoodyak.squeeze(&mut expected);
+
+            let mut hasher = XoodyakHash::new();
+            hasher.update(&data[..len]);
+            let mut reader = hasher.finalize_xof();
+            let mut out = [0u8; 50];
+            for chunk in out.chunks_mut(7) {
+                reader.squeeze(chunk);
+            }
+            assert_eq!(out, expected, "message of {len} bytes");
+        }
+    }
+}
@@ -1,24 +1,104 @@
 #![no_std]
 #![forbid(unsafe_code)]
 
+use alloc::vec::Vec;
+use core::fmt;
+
+#[cfg(feature = "aead")]
+pub use aead;
+#[cfg(feature = "digest")]
+pub use digest;
+pub use hash::{XoodyakHash, XoodyakHashReader, HASH_SIZE};
+use lwc_core::Aead;
+pub use lwc_core::Error;
+#[cfg(feature = "std")]
+pub use lwc_core::{DecryptReader, EncryptWriter};
+pub use session::XoodyakSession;
+pub use stream::{XoodyakDecryptor, XoodyakEncryptor};
+#[cfg(feature = "zeroize")]
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+mod hash;
+#[cfg(feature = "aead")]
+mod rustcrypto;
+#[cfg(feature = "digest")]
+mod rustcrypto_digest;
+mod session;
+mod stream;
 pub mod xoodoo;
 pub mod xoodyak;
 
 extern crate alloc;
 
+/// Longest key ID accepted by [`XoodyakAead::with_key_id`], in bytes: the key, the key ID and its
+/// length have to fit in one keyed absorb block.
+pub const MAX_KEY_ID_SIZE: usize = xoodyak::KEYED_ABSORB_RATE - 16 - 1;
+
 pub struct XoodyakAead {
     key: [u8; 16],
+    key_id: Vec<u8>,
+    counter: Option<Vec<u8>>,
 }
 
 impl XoodyakAead {
-    pub fn new(key: &[u8]) -> Self {
+    pub fn new(key: &[u8; 16]) -> Self {
         Self {
-            key: key[..16].try_into().unwrap(),
+            key: *key,
+            key_id: Vec::new(),
+            counter: None,
+        }
+    }
+
+    /// Absorb `key_id` together with the key, so that devices sharing a master key get
+    /// independent instances.
+    pub fn with_key_id(mut self, key_id: &[u8]) -> Result<Self, Error> {
+        if key_id.len() > MAX_KEY_ID_SIZE {
+            return Err(Error::InvalidKeyIdLength);
         }
+
+        self.key_id = key_id.to_vec();
+        Ok(self)
+    }
+
+    /// Absorb `counter` one byte per block after the key, which is the initialization path
+    /// Xoodyak provides for resistance to side-channel leakage.
+    ///
+    /// An empty counter is the same as no counter.
+    pub fn with_counter(mut self, counter: &[u8]) -> Self {
+        self.counter = Some(counter.to_vec());
+        self
+    }
+
+    /// Start a session in which successive messages are encrypted on the same state.
+    ///
+    /// Both ends create their session with the same nonce, which must not be reused with the key.
+    pub fn session(&self, nonce: &[u8; 16]) -> XoodyakSession {
+        XoodyakSession::new(self.init(nonce))
+    }
+
+    /// Start encrypting a message given in fragments.
+    pub fn encryptor(&self, nonce: &[u8; 16]) -> XoodyakEncryptor {
+        XoodyakEncryptor::new(self.init(nonce))
+    }
+
+    /// Start decrypting a message given in fragments.
+    pub fn decryptor(&self, nonce: &[u8; 16]) -> XoodyakDecryptor {
+        XoodyakDecryptor::new(self.init(nonce))
+    }
+
+    fn init(&self, nonce: &[u8; 16]) -> xoodyak::Xoodyak {
+        let mut xoodyak = xoodyak::Xoodyak::new(&self.key, &self.key_id, self.counter.as_deref());
+        xoodyak.absorb(nonce);
+        xoodyak
+    }
+
+    pub fn new_from_slice(key: &[u8]) -> Result<Self, Error> {
+        let key = key.try_into().map_err(|_| Error::InvalidKeyLength)?;
+        Ok(Self::new(key))
     }
 
-    pub fn encrypt(&mut self, nonce: &[u8], ad: &[u8], plaintext: &mut [u8]) -> [u8; 16] {
-        let mut xoodyak = xoodyak::Xoodyak::new(&self.key, nonce, None);
+    pub fn encrypt(&self, nonce: &[u8; 16], ad: &[u8], plaintext: &mut [u8]) -> [u8; 16] {
+        let mut xoodyak = self.init(nonce);
         xoodyak.absorb(ad);
         xoodyak.encrypt_inplace(plaintext);
         let mut tag = [0u8; 16];
@@ -27,22 +107,81 @@ impl XoodyakAead {
     }
 
     pub fn decrypt(
-        &mut self,
-        nonce: &[u8],
+        &self,
+        nonce: &[u8; 16],
         ad: &[u8],
         ciphertext: &mut [u8],
         expected_tag: &[u8],
-    ) -> Result<(), ()> {
-        let mut xoodyak = xoodyak::Xoodyak::new(&self.key, nonce, None);
+    ) -> Result<(), Error> {
+        if expected_tag.len() != xoodyak::AUTH_TAG_BYTES {
+            return Err(Error::InvalidTagLength);
+        }
+
+        let mut xoodyak = self.init(nonce);
         xoodyak.absorb(ad);
         xoodyak.decrypt_inplace(ciphertext);
-        let mut tag = [0u8; 16];
+        let mut tag = [0u8; xoodyak::AUTH_TAG_BYTES];
         xoodyak.squeeze(&mut tag);
-        if tag == expected_tag {
-            Ok(())
-        } else {
-            Err(())
-        }
+        lwc_core::verify_tag_or_wipe(&tag, expected_tag, ciphertext)
+    }
+}
+
+impl fmt::Debug for XoodyakAead {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("XoodyakAead").finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for XoodyakAead {
+    fn drop(&mut self) {
+        self.key.zeroize();
+        self.key_id.zeroize();
+        self.counter.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl ZeroizeOnDrop for XoodyakAead {}
+
+impl TryFrom<&[u8]> for XoodyakAead {
+    type Error = Error;
+
+    fn try_from(key: &[u8]) -> Result<Self, Self::Error> {
+        Self::new_from_slice(key)
+    }
+}
+
+impl Aead for XoodyakAead {
+    const KEY_SIZE: usize = 16;
+    const NONCE_SIZE: usize = 16;
+    const TAG_SIZE: usize = xoodyak::AUTH_TAG_BYTES;
+
+    type Tag = [u8; xoodyak::AUTH_TAG_BYTES];
+
+    fn new_from_slice(key: &[u8]) -> Result<Self, Error> {
+        Self::new_from_slice(key)
+    }
+
+    fn encrypt_inplace_detached(
+        &self,
+        nonce: &[u8],
+        associated_data: &[u8],
+        buffer: &mut [u8],
+    ) -> Result<Self::Tag, Error> {
+        let nonce = nonce.try_into().map_err(|_| Error::InvalidNonceLength)?;
+        Ok(self.encrypt(nonce, associated_data, buffer))
+    }
+
+    fn decrypt_inplace_detached(
+        &self,
+        nonce: &[u8],
+        associated_data: &[u8],
+        buffer: &mut [u8],
+        tag: &[u8],
+    ) -> Result<(), Error> {
+        let nonce = nonce.try_into().map_err(|_| Error::InvalidNonceLength)?;
+        self.decrypt(nonce, associated_data, buffer, tag)
     }
 }
 
@@ -66,24 +205,128 @@ mod tests {
 
     #[test]
     fn ascon128() {
-        let mut cipher = XoodyakAead::new(&KEY);
+        let cipher = XoodyakAead::new(&KEY);
 
         let mut plaintext = TEST_PLAIN_TEXT.as_bytes().to_vec();
-        let tag = cipher.encrypt(NONCE.as_ref(), &[], &mut plaintext);
+        let tag = cipher.encrypt(&NONCE, &[], &mut plaintext);
         let mut ciphertext = plaintext;
-        assert!(cipher
-            .decrypt(NONCE.as_ref(), &[], &mut ciphertext, &tag)
-            .is_ok());
+        assert!(cipher.decrypt(&NONCE, &[], &mut ciphertext, &tag).is_ok());
 
         assert_eq!(TEST_PLAIN_TEXT.as_bytes(), &ciphertext);
 
         let mut plaintext = TEST_LONG_PLAIN_TEXT.as_bytes().to_vec();
-        let tag = cipher.encrypt(NONCE.as_ref(), &[], &mut plaintext);
+        let tag = cipher.encrypt(&NONCE, &[], &mut plaintext);
         let mut ciphertext = plaintext;
+        assert!(cipher.decrypt(&NONCE, &[], &mut ciphertext, &tag).is_ok());
+
+        assert_eq!(TEST_LONG_PLAIN_TEXT.as_bytes(), &ciphertext);
+    }
+
+    #[test]
+    fn xoodyak_aead() {
+        let cipher = XoodyakAead::new(&KEY);
+
+        let mut buffer = TEST_LONG_PLAIN_TEXT.as_bytes().to_vec();
+        cipher
+            .encrypt_inplace(&NONCE, b"header", &mut buffer)
+            .unwrap();
+        assert_eq!(
+            buffer.len(),
+            TEST_LONG_PLAIN_TEXT.len() + XoodyakAead::TAG_SIZE
+        );
+
         assert!(cipher
-            .decrypt(NONCE.as_ref(), &[], &mut ciphertext, &tag)
+            .decrypt_inplace(&NONCE, b"header", &mut buffer)
             .is_ok());
+        assert_eq!(TEST_LONG_PLAIN_TEXT.as_bytes(), &buffer);
+    }
 
-        assert_eq!(TEST_LONG_PLAIN_TEXT.as_bytes(), &ciphertext);
+    #[test]
+    fn xoodyak_forged_tag() {
+        let cipher = XoodyakAead::new(&KEY);
+
+        let mut buffer = TEST_PLAIN_TEXT.as_bytes().to_vec();
+        let mut tag = cipher.encrypt(&NONCE, &[], &mut buffer);
+        let ciphertext = buffer.clone();
+        assert_eq!(
+            cipher.decrypt(&NONCE, &[], &mut buffer, &tag[..15]),
+            Err(Error::InvalidTagLength)
+        );
+        assert_eq!(buffer, ciphertext);
+
+        tag[0] ^= 1;
+        assert_eq!(
+            cipher.decrypt(&NONCE, &[], &mut buffer, &tag),
+            Err(Error::AuthenticationFailed)
+        );
+        assert!(buffer.iter().all(|&byte| byte == 0));
+    }
+
+    #[test]
+    fn xoodyak_lengths() {
+        assert_eq!(
+            XoodyakAead::new_from_slice(&KEY[..15]).err(),
+            Some(Error::InvalidKeyLength)
+        );
+        assert_eq!(
+            XoodyakAead::try_from([0u8; 17].as_ref()).err(),
+            Some(Error::InvalidKeyLength)
+        );
+
+        let cipher = XoodyakAead::new_from_slice(&KEY).unwrap();
+        let mut buffer = [0u8; 16];
+        assert_eq!(
+            cipher.encrypt_inplace_detached(&NONCE[..12], &[], &mut buffer),
+            Err(Error::InvalidNonceLength)
+        );
+        assert_eq!(
+            cipher.decrypt_inplace_detached(&[0u8; 17], &[], &mut buffer, &[0u8; 16]),
+            Err(Error::InvalidNonceLength)
+        );
+    }
+
+    #[test]
+    fn xoodyak_key_id_and_counter() {
+        let cipher = XoodyakAead::new(&KEY);
+        let with_id = XoodyakAead::new(&KEY).with_key_id(b"device").unwrap();
+        let with_counter = XoodyakAead::new(&KEY).with_counter(&[0x01, 0x02]);
+
+        let tag = cipher.encrypt(&NONCE, &[], &mut []);
+        assert_ne!(with_id.encrypt(&NONCE, &[], &mut []), tag);
+        assert_ne!(with_counter.encrypt(&NONCE, &[], &mut []), tag);
+        assert_eq!(
+            with_id.decrypt(&NONCE, &[], &mut [], &tag),
+            Err(Error::AuthenticationFailed)
+        );
+
+        let mut buffer = TEST_PLAIN_TEXT.as_bytes().to_vec();
+        let tag = with_id.encrypt(&NONCE, b"header", &mut buffer);
+        assert!(with_id
+            .decrypt(&NONCE, b"header", &mut buffer, &tag)
+            .is_ok());
+        assert_eq!(TEST_PLAIN_TEXT.as_bytes(), &buffer);
+
+        let empty_counter = XoodyakAead::new(&KEY).with_counter(&[]);
+        assert_eq!(
+            empty_counter.encrypt(&NONCE, &[], &mut []),
+            cipher.encrypt(&NONCE, &[], &mut [])
+        );
+
+        assert!(XoodyakAead::new(&KEY)
+            .with_key_id(&[0u8; MAX_KEY_ID_SIZE])
+            .is_ok());
+        assert_eq!(
+            XoodyakAead::new(&KEY)
+                .with_key_id(&[0u8; MAX_KEY_ID_SIZE + 1])
+                .err(),
+            Some(Error::InvalidKeyIdLength)
+        );
+    }
+
+    #[test]
+    fn xoodyak_debug_is_redacted() {
+        let cipher = XoodyakAead::new(&[0xab; 16]);
+        let debug = alloc::format!("{cipher:?}");
+        assert_eq!(debug, "XoodyakAead { .. }");
     }
 }
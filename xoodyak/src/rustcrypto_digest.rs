@@ -0,0 +1,84 @@
+use digest::consts::U32;
+use digest::{
+    ExtendableOutput, FixedOutput, FixedOutputReset, HashMarker, Output, OutputSizeUser, Reset,
+    Update, XofReader,
+};
+
+use crate::{XoodyakHash, XoodyakHashReader};
+
+impl HashMarker for XoodyakHash {}
+
+impl OutputSizeUser for XoodyakHash {
+    type OutputSize = U32;
+}
+
+impl Update for XoodyakHash {
+    fn update(&mut self, data: &[u8]) {
+        XoodyakHash::update(self, data);
+    }
+}
+
+impl FixedOutput for XoodyakHash {
+    fn finalize_into(self, out: &mut Output<Self>) {
+        out.copy_from_slice(&XoodyakHash::finalize(self));
+    }
+}
+
+impl Reset for XoodyakHash {
+    fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
+
+impl FixedOutputReset for XoodyakHash {
+    fn finalize_into_reset(&mut self, out: &mut Output<Self>) {
+        out.copy_from_slice(&core::mem::take(self).finalize());
+    }
+}
+
+impl ExtendableOutput for XoodyakHash {
+    type Reader = XoodyakHashReader;
+
+    fn finalize_xof(self) -> Self::Reader {
+        XoodyakHash::finalize_xof(self)
+    }
+}
+
+impl XofReader for XoodyakHashReader {
+    fn read(&mut self, buffer: &mut [u8]) {
+        self.squeeze(buffer);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use digest::Digest;
+
+    use super::*;
+
+    #[test]
+    fn rustcrypto_digest() {
+        let expected = XoodyakHash::digest(b"Hello, world!");
+
+        let mut hasher = <XoodyakHash as Digest>::new();
+        Digest::update(&mut hasher, b"Hello, ");
+        Digest::update(&mut hasher, b"world!");
+        assert_eq!(hasher.finalize_reset()[..], expected);
+
+        Digest::update(&mut hasher, b"Hello, world!");
+        assert_eq!(Digest::finalize(hasher)[..], expected);
+    }
+
+    #[test]
+    fn rustcrypto_xof() {
+        let mut expected = [0u8; 40];
+        let mut hasher = XoodyakHash::new();
+        hasher.update(b"Hello, world!");
+        hasher.finalize_xof().squeeze(&mut expected);
+        assert_eq!(expected[..32], XoodyakHash::digest(b"Hello, world!"));
+
+        let mut out = [0u8; 40];
+        XoodyakHash::digest_xof(b"Hello, world!", &mut out);
+        assert_eq!(out, expected);
+    }
+}
@@ -0,0 +1,151 @@
+use core::fmt;
+
+use crate::xoodyak::{Xoodyak, AUTH_TAG_BYTES};
+use crate::Error;
+
+/// A sequence of messages encrypted on one evolving Cyclist state, each with its own tag.
+///
+/// Every tag depends on all the messages before it, so messages have to be decrypted in the order
+/// they were encrypted: a reordered, replayed or forged message fails to authenticate. A failed
+/// decryption leaves the session as it was, so the next genuine message is still accepted.
+///
+/// Created with [`XoodyakAead::session`](crate::XoodyakAead::session).
+pub struct XoodyakSession {
+    xoodyak: Xoodyak,
+}
+
+impl XoodyakSession {
+    pub(crate) fn new(xoodyak: Xoodyak) -> Self {
+        Self { xoodyak }
+    }
+
+    /// Encrypt the next message in place and return its tag.
+    pub fn encrypt(&mut self, ad: &[u8], plaintext: &mut [u8]) -> [u8; AUTH_TAG_BYTES] {
+        self.xoodyak.absorb(ad);
+        self.xoodyak.encrypt_inplace(plaintext);
+        let mut tag = [0u8; AUTH_TAG_BYTES];
+        self.xoodyak.squeeze(&mut tag);
+        tag
+    }
+
+    /// Decrypt the next message in place, wiping it if the tag does not match.
+    pub fn decrypt(
+        &mut self,
+        ad: &[u8],
+        ciphertext: &mut [u8],
+        expected_tag: &[u8],
+    ) -> Result<(), Error> {
+        if expected_tag.len() != AUTH_TAG_BYTES {
+            return Err(Error::InvalidTagLength);
+        }
+
+        let mut xoodyak = self.xoodyak.clone();
+        xoodyak.absorb(ad);
+        xoodyak.decrypt_inplace(ciphertext);
+        let mut tag = [0u8; AUTH_TAG_BYTES];
+        xoodyak.squeeze(&mut tag);
+        lwc_core::verify_tag_or_wipe(&tag, expected_tag, ciphertext)?;
+
+        self.xoodyak = xoodyak;
+        Ok(())
+    }
+}
+
+impl fmt::Debug for XoodyakSession {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("XoodyakSession").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use super::*;
+    use crate::XoodyakAead;
+
+    const KEY: [u8; 16] = [0x0f; 16];
+    const NONCE: [u8; 16] = [0x1e; 16];
+
+    fn encrypt_messages(messages: &[&[u8]]) -> Vec<(Vec<u8>, [u8; AUTH_TAG_BYTES])> {
+        let mut session = XoodyakAead::new(&KEY).session(&NONCE);
+        messages
+            .iter()
+            .map(|message| {
+                let mut buffer = message.to_vec();
+                let tag = session.encrypt(b"telemetry", &mut buffer);
+                (buffer, tag)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn in_order() {
+        let messages: [&[u8]; 3] = [b"first", b"", b"a somewhat longer third message"];
+        let encrypted = encrypt_messages(&messages);
+
+        // The first message is the same as a one-shot encryption with the same nonce.
+        let mut buffer = messages[0].to_vec();
+        let tag = XoodyakAead::new(&KEY).encrypt(&NONCE, b"telemetry", &mut buffer);
+        assert_eq!((buffer, tag), encrypted[0]);
+
+        let mut session = XoodyakAead::new(&KEY).session(&NONCE);
+        for (message, (ciphertext, tag)) in messages.iter().zip(&encrypted) {
+            let mut buffer = ciphertext.clone();
+            assert_eq!(session.decrypt(b"telemetry", &mut buffer, tag), Ok(()));
+            assert_eq!(&buffer, message);
+        }
+    }
+
+    #[test]
+    fn same_message_twice() {
+        // Repeating a message within a session does not repeat its ciphertext.
+        let encrypted = encrypt_messages(&[b"ping", b"ping"]);
+        assert_ne!(encrypted[0], encrypted[1]);
+    }
+
+    #[test]
+    fn reordered_and_replayed() {
+        let encrypted = encrypt_messages(&[b"first", b"second"]);
+        let mut session = XoodyakAead::new(&KEY).session(&NONCE);
+
+        let (ciphertext, tag) = &encrypted[1];
+        let mut buffer = ciphertext.clone();
+        assert_eq!(
+            session.decrypt(b"telemetry", &mut buffer, tag),
+            Err(Error::AuthenticationFailed)
+        );
+        assert!(buffer.iter().all(|&byte| byte == 0));
+
+        let (ciphertext, tag) = &encrypted[0];
+        let mut buffer = ciphertext.clone();
+        assert_eq!(session.decrypt(b"telemetry", &mut buffer, tag), Ok(()));
+        let mut buffer = ciphertext.clone();
+        assert_eq!(
+            session.decrypt(b"telemetry", &mut buffer, tag),
+            Err(Error::AuthenticationFailed)
+        );
+
+        let (ciphertext, tag) = &encrypted[1];
+        let mut buffer = ciphertext.clone();
+        assert_eq!(session.decrypt(b"telemetry", &mut buffer, tag), Ok(()));
+        assert_eq!(buffer, b"second");
+    }
+
+    #[test]
+    fn wrong_associated_data() {
+        let encrypted = encrypt_messages(&[b"first"]);
+        let mut session = XoodyakAead::new(&KEY).session(&NONCE);
+        let (ciphertext, tag) = &encrypted[0];
+
+        let mut buffer = ciphertext.clone();
+        assert_eq!(
+            session.decrypt(b"other", &mut buffer, tag),
+            Err(Error::AuthenticationFailed)
+        );
+        assert_eq!(
+            session.decrypt(b"telemetry", &mut buffer, &tag[..8]),
+            Err(Error::InvalidTagLength)
+        );
+    }
+}
@@ -0,0 +1,283 @@
+use core::fmt;
+
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+use crate::xoodyak::{Xoodyak, AUTH_TAG_BYTES, KEYED_ABSORB_RATE, KEYED_SQUEEZE_RATE};
+use crate::Error;
+
+/// State shared by [`XoodyakEncryptor`] and [`XoodyakDecryptor`].
+///
+/// Cyclist pads every block with its length, so the last block of the associated data and every
+/// block of the message are only absorbed once it is known how long they are. `position` counts
+/// the bytes of the current block held in `ad` or `plaintext`.
+struct Stream {
+    xoodyak: Xoodyak,
+    ad: [u8; KEYED_ABSORB_RATE],
+    keystream: [u8; KEYED_SQUEEZE_RATE],
+    plaintext: [u8; KEYED_SQUEEZE_RATE],
+    position: usize,
+    first: bool,
+    in_message: bool,
+    block_open: bool,
+}
+
+impl Stream {
+    fn new(xoodyak: Xoodyak) -> Self {
+        Self {
+            xoodyak,
+            ad: [0u8; KEYED_ABSORB_RATE],
+            keystream: [0u8; KEYED_SQUEEZE_RATE],
+            plaintext: [0u8; KEYED_SQUEEZE_RATE],
+            position: 0,
+            first: true,
+            in_message: false,
+            block_open: false,
+        }
+    }
+
+    fn update_ad(&mut self, mut ad: &[u8]) -> Result<(), Error> {
+        if self.in_message {
+            return Err(Error::AssociatedDataAfterMessage);
+        }
+
+        while !ad.is_empty() {
+            if self.position == KEYED_ABSORB_RATE {
+                let block = self.ad;
+                self.xoodyak.absorb_block(&block, self.first);
+                self.first = false;
+                self.position = 0;
+            }
+
+            let len = ad.len().min(KEYED_ABSORB_RATE - self.position);
+            self.ad[self.position..self.position + len].copy_from_slice(&ad[..len]);
+            self.position += len;
+            ad = &ad[len..];
+        }
+        Ok(())
+    }
+
+    fn start_message(&mut self) {
+        if !self.in_message {
+            let block = self.ad;
+            self.xoodyak
+                .absorb_block(&block[..self.position], self.first);
+            self.position = 0;
+            self.first = true;
+            self.in_message = true;
+        }
+    }
+
+    fn update(&mut self, mut buffer: &mut [u8], decrypt: bool) {
+        self.start_message();
+        while !buffer.is_empty() {
+            if !self.block_open {
+                self.xoodyak.crypt_up(&mut self.keystream, self.first);
+                self.first = false;
+                self.block_open = true;
+                self.position = 0;
+            }
+
+            let len = buffer.len().min(KEYED_SQUEEZE_RATE - self.position);
+            let (chunk, rest) = core::mem::take(&mut buffer).split_at_mut(len);
+            let keystream = &self.keystream[self.position..self.position + len];
+            let plaintext = &mut self.plaintext[self.position..self.position + len];
+            for ((byte, key), plain) in chunk.iter_mut().zip(keystream).zip(plaintext) {
+                *plain = if decrypt { *byte ^ key } else { *byte };
+                *byte ^= key;
+            }
+            self.position += len;
+            if self.position == KEYED_SQUEEZE_RATE {
+                self.xoodyak.crypt_down(&self.plaintext);
+                self.block_open = false;
+            }
+            buffer = rest;
+        }
+    }
+
+    fn finalize(mut self) -> [u8; AUTH_TAG_BYTES] {
+        self.start_message();
+        if self.block_open {
+            self.xoodyak.crypt_down(&self.plaintext[..self.position]);
+        } else if self.first {
+            // An empty message is still one block.
+            self.xoodyak.crypt_up(&mut self.keystream, true);
+            self.xoodyak.crypt_down(&[]);
+        }
+
+        let mut tag = [0u8; AUTH_TAG_BYTES];
+        self.xoodyak.squeeze(&mut tag);
+        tag
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for Stream {
+    fn drop(&mut self) {
+        self.ad.zeroize();
+        self.keystream.zeroize();
+        self.plaintext.zeroize();
+    }
+}
+
+/// Encrypts a message given in fragments of any length, producing the same ciphertext and tag as
+/// [`XoodyakAead::encrypt`](crate::XoodyakAead::encrypt).
+///
+/// Created with [`XoodyakAead::encryptor`](crate::XoodyakAead::encryptor).
+pub struct XoodyakEncryptor {
+    stream: Stream,
+}
+
+impl XoodyakEncryptor {
+    pub(crate) fn new(xoodyak: Xoodyak) -> Self {
+        Self {
+            stream: Stream::new(xoodyak),
+        }
+    }
+
+    /// Absorb the next fragment of the associated data.
+    ///
+    /// Fails with [`Error::AssociatedDataAfterMessage`] once the message has started.
+    pub fn update_ad(&mut self, ad: &[u8]) -> Result<(), Error> {
+        self.stream.update_ad(ad)
+    }
+
+    /// Encrypt the next fragment of the message in place.
+    pub fn update(&mut self, buffer: &mut [u8]) {
+        self.stream.update(buffer, false);
+    }
+
+    /// Return the tag of the associated data and message.
+    pub fn finalize(self) -> [u8; AUTH_TAG_BYTES] {
+        self.stream.finalize()
+    }
+}
+
+impl fmt::Debug for XoodyakEncryptor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("XoodyakEncryptor").finish_non_exhaustive()
+    }
+}
+
+/// Decrypts a message given in fragments of any length.
+///
+/// The plaintext is handed back before the tag can be checked, so it must not be trusted until
+/// [`finalize`](Self::finalize) succeeds.
+///
+/// Created with [`XoodyakAead::decryptor`](crate::XoodyakAead::decryptor).
+pub struct XoodyakDecryptor {
+    stream: Stream,
+}
+
+impl XoodyakDecryptor {
+    pub(crate) fn new(xoodyak: Xoodyak) -> Self {
+        Self {
+            stream: Stream::new(xoodyak),
+        }
+    }
+
+    /// Absorb the next fragment of the associated data.
+    ///
+    /// Fails with [`Error::AssociatedDataAfterMessage`] once the message has started.
+    pub fn update_ad(&mut self, ad: &[u8]) -> Result<(), Error> {
+        self.stream.update_ad(ad)
+    }
+
+    /// Decrypt the next fragment of the message in place.
+    pub fn update(&mut self, buffer: &mut [u8]) {
+        self.stream.update(buffer, true);
+    }
+
+    /// Check the tag of the associated data and message.
+    pub fn finalize(self, expected_tag: &[u8]) -> Result<(), Error> {
+        if expected_tag.len() != AUTH_TAG_BYTES {
+            return Err(Error::InvalidTagLength);
+        }
+
+        lwc_core::verify_tag(&self.stream.finalize(), expected_tag)
+    }
+}
+
+impl fmt::Debug for XoodyakDecryptor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("XoodyakDecryptor").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::XoodyakAead;
+
+    const KEY: [u8; 16] = [0x42; 16];
+    const NONCE: [u8; 16] = [0x24; 16];
+
+    #[test]
+    fn matches_one_shot() {
+        let cipher = XoodyakAead::new(&KEY);
+        let ad: [u8; 100] = core::array::from_fn(|i| i as u8);
+        let plaintext: [u8; 77] = core::array::from_fn(|i| (3 * i) as u8);
+
+        let mut expected = plaintext;
+        let expected_tag = cipher.encrypt(&NONCE, &ad, &mut expected);
+
+        for split in [0, 1, 5, 24, 44, 50] {
+            let mut encryptor = cipher.encryptor(&NONCE);
+            for chunk in ad.chunks(split.max(1)) {
+                encryptor.update_ad(chunk).unwrap();
+            }
+            let mut buffer = plaintext;
+            let (head, tail) = buffer.split_at_mut(split);
+            encryptor.update(head);
+            encryptor.update(&mut []);
+            encryptor.update(tail);
+            assert_eq!(buffer, expected);
+            assert_eq!(encryptor.finalize(), expected_tag);
+
+            let mut decryptor = cipher.decryptor(&NONCE);
+            decryptor.update_ad(&ad).unwrap();
+            for chunk in buffer.chunks_mut(split.max(1)) {
+                decryptor.update(chunk);
+            }
+            assert_eq!(buffer, plaintext);
+            assert_eq!(decryptor.finalize(&expected_tag), Ok(()));
+        }
+    }
+
+    #[test]
+    fn empty_message() {
+        let cipher = XoodyakAead::new(&KEY);
+        let expected_tag = cipher.encrypt(&NONCE, b"header", &mut []);
+
+        let mut encryptor = cipher.encryptor(&NONCE);
+        encryptor.update_ad(b"head").unwrap();
+        encryptor.update_ad(b"er").unwrap();
+        assert_eq!(encryptor.finalize(), expected_tag);
+    }
+
+    #[test]
+    fn forged_tag() {
+        let cipher = XoodyakAead::new(&KEY);
+        let mut buffer = *b"message";
+        let mut tag = cipher.encrypt(&NONCE, &[], &mut buffer);
+        tag[0] ^= 1;
+
+        let mut decryptor = cipher.decryptor(&NONCE);
+        decryptor.update(&mut buffer);
+        assert_eq!(
+            cipher.decryptor(&NONCE).finalize(&tag[..8]),
+            Err(Error::InvalidTagLength)
+        );
+        assert_eq!(decryptor.finalize(&tag), Err(Error::AuthenticationFailed));
+    }
+
+    #[test]
+    fn late_associated_data() {
+        let mut encryptor = XoodyakAead::new(&KEY).encryptor(&NONCE);
+        encryptor.update(&mut [0u8; 4]);
+        assert_eq!(
+            encryptor.update_ad(b"header"),
+            Err(Error::AssociatedDataAfterMessage)
+        );
+    }
+}
@@ -1,4 +1,10 @@
+use core::fmt;
+
 use rawbytes::RawBytes;
+#[cfg(feature = "zeroize")]
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::Error;
 
 #[inline(always)]
 const fn round(x: [u32; 12], c: u32) -> [u32; 12] {
@@ -47,12 +53,36 @@ const fn round(x: [u32; 12], c: u32) -> [u32; 12] {
     ]
 }
 
-#[derive(Clone, Copy, Debug, Default)]
+#[derive(Clone, Default)]
 pub struct State {
     x: [u32; 12],
 }
 
+impl fmt::Debug for State {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("State").finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Zeroize for State {
+    fn zeroize(&mut self) {
+        self.x.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for State {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl ZeroizeOnDrop for State {}
+
 impl State {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         x0: u32,
         x1: u32,
@@ -128,7 +158,7 @@ impl State {
 }
 
 impl TryFrom<&[u32]> for State {
-    type Error = ();
+    type Error = Error;
 
     fn try_from(values: &[u32]) -> Result<Self, Self::Error> {
         match values.len() {
@@ -138,7 +168,7 @@ impl TryFrom<&[u32]> for State {
                     values[7], values[8], values[9], values[10], values[11],
                 ],
             }),
-            _ => Err(()),
+            _ => Err(Error::InvalidStateLength),
         }
     }
 }
@@ -150,11 +180,11 @@ impl From<&[u32; 12]> for State {
 }
 
 impl TryFrom<&[u8]> for State {
-    type Error = ();
+    type Error = Error;
 
     fn try_from(values: &[u8]) -> Result<Self, Self::Error> {
         if values.len() != core::mem::size_of::<u32>() * 12 {
-            return Err(());
+            return Err(Error::InvalidStateLength);
         }
         let mut state = State::default();
         for (src, dst) in values
@@ -367,4 +397,12 @@ mod test {
         state.add_bytes(&input);
         assert_eq!(state, State::from(&expected));
     }
+
+    #[cfg(feature = "zeroize")]
+    #[test]
+    fn test_zeroize() {
+        let mut state = State::from(&[0xa5u8; 48]);
+        state.zeroize();
+        assert_eq!(state, State::default());
+    }
 }
@@ -1,30 +1,68 @@
 use alloc::vec;
 
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
 use crate::xoodoo::State;
 
 pub const KEYED_ABSORB_RATE: usize = 44;
 pub const KEYED_SQUEEZE_RATE: usize = 24;
+pub const HASH_RATE: usize = 16;
 pub const AUTH_TAG_BYTES: usize = 16;
+/// Number of bytes squeezed and absorbed back by [`Xoodyak::ratchet`].
+pub const RATCHET_BYTES: usize = 16;
 
-#[derive(PartialEq)]
+#[derive(Clone, Copy, PartialEq)]
 enum Phase {
     Up,
     Down,
 }
 
+#[derive(Clone, Copy, PartialEq)]
+enum Mode {
+    Hash,
+    Keyed,
+}
+
+/// The Cyclist object of Xoodyak.
+///
+/// It starts in hash mode when created with an empty key and in keyed mode otherwise. Encryption,
+/// decryption, [`squeeze_key`](Self::squeeze_key) and [`ratchet`](Self::ratchet) are only
+/// defined in keyed mode.
+#[derive(Clone)]
 pub struct Xoodyak {
     state: State,
     phase: Phase,
+    mode: Mode,
+    absorb_rate: usize,
+    squeeze_rate: usize,
 }
 
 impl Xoodyak {
-    pub fn new(key: &[u8], nonce: &[u8], counter: Option<&[u8]>) -> Self {
-        let mut xoodyak = Self {
+    /// Initialize Cyclist with a key, a key ID and an optional counter.
+    ///
+    /// An empty `key` gives an instance in hash mode, like [`new_hash`](Self::new_hash).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `key` and `id` together are longer than 43 bytes.
+    pub fn new(key: &[u8], id: &[u8], counter: Option<&[u8]>) -> Self {
+        let mut xoodyak = Self::new_hash();
+        if !key.is_empty() {
+            xoodyak.absorb_key(key, id, counter);
+        }
+        xoodyak
+    }
+
+    /// Create an instance in hash mode, which is Cyclist initialized with an empty key.
+    pub fn new_hash() -> Self {
+        Self {
             state: State::default(),
             phase: Phase::Up,
-        };
-        xoodyak.absorb_key_and_nonce(key, nonce, counter);
-        xoodyak
+            mode: Mode::Hash,
+            absorb_rate: HASH_RATE,
+            squeeze_rate: HASH_RATE,
+        }
     }
 
     fn set_phase(&mut self, phase: Phase) {
@@ -34,7 +72,9 @@ impl Xoodyak {
     #[inline(always)]
     fn up(&mut self, out: Option<&mut [u8]>, cu: u8) {
         self.set_phase(Phase::Up);
-        self.state.add_byte(cu, 47);
+        if self.mode == Mode::Keyed {
+            self.state.add_byte(cu, 47);
+        }
         self.state.permute();
         if let Some(out) = out {
             self.state.extract_bytes(out);
@@ -50,6 +90,11 @@ impl Xoodyak {
         } else {
             self.state.add_byte(0x01, 0);
         }
+        // Hash mode only keeps the bit that separates the first block of a string from the others.
+        let cd = match self.mode {
+            Mode::Hash => cd & 0x01,
+            Mode::Keyed => cd,
+        };
         self.state.add_byte(cd, 47);
     }
 
@@ -66,13 +111,57 @@ impl Xoodyak {
         }
     }
 
+    /// Absorb `data` as one string.
     pub fn absorb(&mut self, data: &[u8]) {
-        self.absorb_any(data, 16, 0x03);
+        self.absorb_any(data, self.absorb_rate, 0x03);
+    }
+
+    /// Absorb one block of at most the absorb rate, as part of a string fed in several calls.
+    ///
+    /// `first` marks the first block of the string; a string of `n` blocks absorbed this way
+    /// gives the same state as a single call to [`absorb`](Self::absorb).
+    pub(crate) fn absorb_block(&mut self, block: &[u8], first: bool) {
+        debug_assert!(block.len() <= self.absorb_rate);
+        if self.phase != Phase::Up {
+            self.up(None, 0x00);
+        }
+        self.down(Some(block), if first { 0x03 } else { 0x00 });
+    }
+
+    /// Squeeze one block of at most the squeeze rate, as part of an output read in several calls.
+    ///
+    /// `first` marks the first block of the output; reading `n` blocks this way gives the same
+    /// bytes as a single call to [`squeeze`](Self::squeeze).
+    pub(crate) fn squeeze_block(&mut self, out: &mut [u8], first: bool) {
+        debug_assert!(out.len() <= self.squeeze_rate);
+        if first {
+            self.up(Some(out), 0x40);
+        } else {
+            self.down(None, 0x00);
+            self.up(Some(out), 0x00);
+        }
+    }
+
+    /// Start a block of encryption or decryption and write its keystream to `keystream`.
+    ///
+    /// `first` marks the first block of the message. Each block is finished by
+    /// [`crypt_down`](Self::crypt_down) with its plaintext, so a message processed in blocks this
+    /// way gives the same state as [`encrypt_inplace`](Self::encrypt_inplace); an empty message
+    /// is a single block with no plaintext.
+    pub(crate) fn crypt_up(&mut self, keystream: &mut [u8; KEYED_SQUEEZE_RATE], first: bool) {
+        self.assert_keyed("crypt_up");
+        self.up(Some(keystream), if first { 0x80 } else { 0x00 });
+    }
+
+    /// Finish a block started by [`crypt_up`](Self::crypt_up) by absorbing its plaintext.
+    pub(crate) fn crypt_down(&mut self, plaintext: &[u8]) {
+        debug_assert!(plaintext.len() <= KEYED_SQUEEZE_RATE);
+        self.down(Some(plaintext), 0x00);
     }
 
     #[inline]
     fn squeeze_any(&mut self, out: &mut [u8], cu: u8) {
-        let mut chunks_it = out.chunks_mut(KEYED_SQUEEZE_RATE);
+        let mut chunks_it = out.chunks_mut(self.squeeze_rate);
         self.up(chunks_it.next(), cu);
         for chunk in chunks_it {
             self.down(None, 0x00);
@@ -80,25 +169,82 @@ impl Xoodyak {
         }
     }
 
+    /// Squeeze `out.len()` bytes of output.
     pub fn squeeze(&mut self, out: &mut [u8]) {
-        self.squeeze_any(out, 0x80);
+        self.squeeze_any(out, 0x40);
     }
 
-    fn absorb_key_and_nonce(&mut self, key: &[u8], nonce: &[u8], counter: Option<&[u8]>) {
+    fn assert_keyed(&self, operation: &str) {
+        assert!(
+            self.mode == Mode::Keyed,
+            "Xoodyak::{operation} is only available in keyed mode"
+        );
+    }
+
+    /// Squeeze `out.len()` bytes meant to be used as a key.
+    ///
+    /// # Panics
+    ///
+    /// Panics in hash mode.
+    pub fn squeeze_key(&mut self, out: &mut [u8]) {
+        self.assert_keyed("squeeze_key");
+        self.squeeze_any(out, 0x20);
+    }
+
+    /// Overwrite part of the state with zeros so that earlier states cannot be recovered from
+    /// later ones, which gives forward secrecy.
+    ///
+    /// # Panics
+    ///
+    /// Panics in hash mode.
+    pub fn ratchet(&mut self) {
+        self.assert_keyed("ratchet");
+        let mut buffer = [0u8; RATCHET_BYTES];
+        self.squeeze_any(&mut buffer, 0x10);
+        self.absorb_any(&buffer, self.absorb_rate, 0x00);
+        #[cfg(feature = "zeroize")]
+        buffer.zeroize();
+    }
+
+    fn absorb_key(&mut self, key: &[u8], id: &[u8], counter: Option<&[u8]>) {
+        assert!(
+            key.len() + id.len() < KEYED_ABSORB_RATE,
+            "key and key ID must fit in one block"
+        );
+        self.mode = Mode::Keyed;
+        self.absorb_rate = KEYED_ABSORB_RATE;
+        self.squeeze_rate = KEYED_SQUEEZE_RATE;
+
         let key_len = key.len();
-        let nonce_len = nonce.len();
-        // IV: key || nonce || nonce_len
-        let mut iv = vec![0u8; key_len + nonce_len + 1];
-        iv[..key_len].copy_from_slice(key);
-        iv[key_len..key_len + nonce_len].copy_from_slice(nonce);
-        iv[key_len + nonce_len] = nonce_len as u8;
-        self.absorb_any(&iv, KEYED_ABSORB_RATE, 0x02);
-        if let Some(counter) = counter {
+        let id_len = id.len();
+        // key || id || id_len
+        let mut key_id = vec![0u8; key_len + id_len + 1];
+        key_id[..key_len].copy_from_slice(key);
+        key_id[key_len..key_len + id_len].copy_from_slice(id);
+        key_id[key_len + id_len] = id_len as u8;
+        self.absorb_any(&key_id, KEYED_ABSORB_RATE, 0x02);
+        #[cfg(feature = "zeroize")]
+        key_id.zeroize();
+        // An empty counter is not absorbed at all, as in the Cyclist specification.
+        if let Some(counter) = counter.filter(|counter| !counter.is_empty()) {
             self.absorb_any(counter, 1, 0x00);
         }
     }
 
+    /// Encrypt `in_out` in place.
+    ///
+    /// # Panics
+    ///
+    /// Panics in hash mode.
     pub fn encrypt_inplace(&mut self, in_out: &mut [u8]) {
+        self.assert_keyed("encrypt_inplace");
+        if in_out.is_empty() {
+            // An empty message still takes one Up/Down round, which the loop below would skip.
+            self.up(None, 0x80);
+            self.down(None, 0x00);
+            return;
+        }
+
         let mut tmp = [0u8; KEYED_SQUEEZE_RATE];
         let mut cu = 0x80;
         for in_out_chunk in in_out.chunks_mut(KEYED_SQUEEZE_RATE) {
@@ -111,7 +257,20 @@ impl Xoodyak {
         }
     }
 
+    /// Decrypt `in_out` in place.
+    ///
+    /// # Panics
+    ///
+    /// Panics in hash mode.
     pub fn decrypt_inplace(&mut self, in_out: &mut [u8]) {
+        self.assert_keyed("decrypt_inplace");
+        if in_out.is_empty() {
+            // An empty message still takes one Up/Down round, which the loop below would skip.
+            self.up(None, 0x80);
+            self.down(None, 0x00);
+            return;
+        }
+
         let mut tmp = [0u8; KEYED_SQUEEZE_RATE];
         let mut cu = 0x80;
         for in_out_chunk in in_out.chunks_mut(KEYED_SQUEEZE_RATE) {
@@ -142,4 +301,39 @@ mod test {
 
         assert_eq!(data, [0u8; 24]);
     }
+
+    #[test]
+    fn test_empty_key_and_counter() {
+        let mut hash = Xoodyak::new(&[], &[], None);
+        let mut expected = [0u8; 32];
+        Xoodyak::new_hash().squeeze(&mut expected);
+        let mut out = [0u8; 32];
+        hash.squeeze(&mut out);
+        assert_eq!(out, expected);
+
+        let key = [0u8; 16];
+        Xoodyak::new(&key, &[], None).squeeze(&mut expected);
+        Xoodyak::new(&key, &[], Some(&[])).squeeze(&mut out);
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    fn test_ratchet() {
+        let key = [0u8; 16];
+        let mut xoodyak = Xoodyak::new(&key, &[], None);
+        let mut ratcheted = xoodyak.clone();
+        ratcheted.ratchet();
+
+        let mut expected = [0u8; 16];
+        xoodyak.squeeze(&mut expected);
+        let mut out = [0u8; 16];
+        ratcheted.squeeze(&mut out);
+        assert_ne!(out, expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "only available in keyed mode")]
+    fn test_ratchet_in_hash_mode() {
+        Xoodyak::new_hash().ratchet();
+    }
 }
@@ -0,0 +1,118 @@
+/*
+ * Generator of cyclist.txt and xoodyak_id.txt, run against the XKCP reference implementation of
+ * Xoodyak.
+ *
+ * The XKCP sources are the ones vendored in the xkcp-sys 0.0.5 crate
+ * (https://static.crates.io/crates/xkcp-sys/xkcp-sys-0.0.5.crate, SHA-256
+ * b321bd9a8b59b3ff864934f95e2fc499f716f380391b524fb54085108658da16). From the root of the unpacked
+ * crate, with this file copied next to it:
+ *
+ *     echo '#define XKCP_has_Xoodoo 1' > config.h
+ *     cc -O1 -I. -IXKCP/lib/common -IXKCP/lib/high/Xoodyak -IXKCP/lib/low/Xoodoo \
+ *         -IXKCP/lib/low/Xoodoo/ref -o genkat_cyclist genkat_cyclist.c \
+ *         XKCP/lib/high/Xoodyak/Xoodyak.c XKCP/lib/low/Xoodoo/ref/Xoodoo-reference.c
+ *     ./genkat_cyclist cyclist > cyclist.txt
+ *     ./genkat_cyclist id > xoodyak_id.txt
+ */
+
+#include <stdio.h>
+#include <string.h>
+#include "Xoodyak.h"
+
+static void print_hex(const char *name, const uint8_t *data, size_t len) {
+    printf("%s = ", name);
+    for (size_t i = 0; i < len; i++) printf("%02X", data[i]);
+    printf("\n");
+}
+
+/*
+ * Each vector initializes with a key, key ID and counter, absorbs the associated data, encrypts,
+ * squeezes a key, ratchets, absorbs the plaintext, decrypts the ciphertext again and squeezes a
+ * tag, so that every Cyclist operation depends on all the earlier ones.
+ */
+static void cyclist(void) {
+    uint8_t key[16], msg[70], ad[32], id[8], counter[4];
+    uint8_t ct[70], pt[70], squeezed_key[40], tag[16];
+    Xoodyak_Instance instance;
+
+    for (int i = 0; i < 16; i++) key[i] = i;
+    for (int i = 0; i < 70; i++) msg[i] = i;
+    for (int i = 0; i < 32; i++) ad[i] = i;
+    for (int i = 0; i < 8; i++) id[i] = 0xa0 + i;
+    for (int i = 0; i < 4; i++) counter[i] = 0x30 + i;
+
+    for (size_t i = 0; i < 64; i++) {
+        size_t id_len = i % 8, counter_len = i % 5, ad_len = i > 32 ? 32 : i;
+        size_t msg_len = (i * 3) % 70, squeezed_key_len = (i % 2) ? 40 : 16;
+
+        Xoodyak_Initialize(&instance, key, 16, id, id_len, counter, counter_len);
+        Xoodyak_Absorb(&instance, ad, ad_len);
+        Xoodyak_Encrypt(&instance, msg, ct, msg_len);
+        Xoodyak_SqueezeKey(&instance, squeezed_key, squeezed_key_len);
+        Xoodyak_Ratchet(&instance);
+        Xoodyak_Absorb(&instance, msg, msg_len);
+        Xoodyak_Decrypt(&instance, ct, pt, msg_len);
+        Xoodyak_Squeeze(&instance, tag, 16);
+
+        printf("Count = %zu\n", i + 1);
+        print_hex("Key", key, 16);
+        print_hex("ID", id, id_len);
+        print_hex("Counter", counter, counter_len);
+        print_hex("AD", ad, ad_len);
+        print_hex("PT", msg, msg_len);
+        print_hex("CT", ct, msg_len);
+        print_hex("SK", squeezed_key, squeezed_key_len);
+        print_hex("DT", pt, msg_len);
+        print_hex("Tag", tag, 16);
+        printf("\n");
+    }
+}
+
+/*
+ * AEAD vectors as in genkat_aead.c, but with a key ID and a counter of varying lengths passed to
+ * Xoodyak_Initialize before the nonce is absorbed.
+ */
+static void id(void) {
+    uint8_t key[16], nonce[16], msg[30], ad[32], id[27], counter[8], ct[30 + 16];
+    Xoodyak_Instance instance;
+
+    for (int i = 0; i < 16; i++) key[i] = nonce[i] = i;
+    for (int i = 0; i < 30; i++) msg[i] = i;
+    for (int i = 0; i < 32; i++) ad[i] = i;
+    for (int i = 0; i < 27; i++) id[i] = 0xa0 + i;
+    for (int i = 0; i < 8; i++) counter[i] = 0x30 + i;
+
+    for (size_t i = 0; i < 56; i++) {
+        size_t id_len = i % 28, counter_len = i % 9, msg_len = i % 30, ad_len = (i * 7) % 33;
+
+        Xoodyak_Initialize(&instance, key, 16, id, id_len, counter, counter_len);
+        Xoodyak_Absorb(&instance, nonce, 16);
+        Xoodyak_Absorb(&instance, ad, ad_len);
+        Xoodyak_Encrypt(&instance, msg, ct, msg_len);
+        Xoodyak_Squeeze(&instance, ct + msg_len, 16);
+
+        printf("Count = %zu\n", i + 1);
+        print_hex("Key", key, 16);
+        print_hex("ID", id, id_len);
+        print_hex("Counter", counter, counter_len);
+        print_hex("Nonce", nonce, 16);
+        print_hex("PT", msg, msg_len);
+        print_hex("AD", ad, ad_len);
+        print_hex("CT", ct, msg_len + 16);
+        printf("\n");
+    }
+}
+
+int main(int argc, char **argv) {
+    if (argc == 2 && !strcmp(argv[1], "cyclist")) {
+        cyclist();
+        return 0;
+    }
+    if (argc == 2 && !strcmp(argv[1], "id")) {
+        id();
+        return 0;
+    }
+
+    fprintf(stderr, "usage: %s cyclist|id\n", argv[0]);
+    return 1;
+}
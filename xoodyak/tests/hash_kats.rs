@@ -0,0 +1,28 @@
+//! Known-answer tests against the `LWC_HASH_KAT_256.txt` file of the Xoodyak submission to the
+//! NIST lightweight cryptography project.
+//!
+//! Like the AEAD file, it was regenerated with the `genkat_hash.c` loops on top of the XKCP
+//! reference implementation: each message is absorbed in hash mode and 32 bytes are squeezed.
+
+use lwc_core::kat;
+use xoodyak::XoodyakHash;
+
+#[test]
+fn xoodyak_hash() {
+    let vectors = kat::parse(include_str!("data/xoodyak_hash.txt"));
+    assert_eq!(vectors.len(), 1025);
+
+    for vector in vectors {
+        // Feed the message in uneven pieces to exercise the block buffering.
+        let mut hasher = XoodyakHash::new();
+        for chunk in vector.bytes("Msg").chunks(5) {
+            hasher.update(chunk);
+        }
+        assert_eq!(
+            hasher.finalize()[..],
+            vector.bytes("MD"),
+            "digest of vector {}",
+            vector.count()
+        );
+    }
+}
@@ -0,0 +1,116 @@
+//! Known-answer tests against the `LWC_AEAD_KAT_128_128.txt` file of the Xoodyak submission to the
+//! NIST lightweight cryptography project.
+//!
+//! The file was regenerated with the `genkat_aead.c` loops on top of the XKCP reference
+//! implementation (`lib/high/Xoodyak` with `lib/low/Xoodoo/ref`), which is what the submission's
+//! `crypto_aead` wrapper calls: the key is absorbed with an empty key ID, then the nonce, the
+//! associated data and the message, and the tag is squeezed last.
+//!
+//! `xoodyak_id.txt` follows the same steps with a key ID and a counter passed to
+//! `Xoodyak_Initialize`. It is generated by `data/genkat_cyclist.c`, which names the exact XKCP
+//! sources and how to build it.
+
+use lwc_core::{kat, Aead};
+use xoodyak::XoodyakAead;
+
+fn run_kats<A: Aead>(input: &str, expected_count: usize) {
+    let vectors = kat::parse(input);
+    assert_eq!(vectors.len(), expected_count);
+
+    for vector in vectors {
+        let cipher = A::new_from_slice(&vector.bytes("Key")).unwrap();
+        let nonce = vector.bytes("Nonce");
+        let associated_data = vector.bytes("AD");
+        let plaintext = vector.bytes("PT");
+        let ciphertext = vector.bytes("CT");
+
+        let mut buffer = plaintext.clone();
+        cipher
+            .encrypt_inplace(&nonce, &associated_data, &mut buffer)
+            .unwrap();
+        assert_eq!(
+            buffer,
+            ciphertext,
+            "encryption of vector {}",
+            vector.count()
+        );
+
+        cipher
+            .decrypt_inplace(&nonce, &associated_data, &mut buffer)
+            .unwrap_or_else(|err| panic!("decryption of vector {}: {err}", vector.count()));
+        assert_eq!(buffer, plaintext, "decryption of vector {}", vector.count());
+    }
+}
+
+#[test]
+fn xoodyak() {
+    run_kats::<XoodyakAead>(include_str!("data/xoodyak.txt"), 1089);
+}
+
+#[test]
+fn xoodyak_key_id_and_counter() {
+    let vectors = kat::parse(include_str!("data/xoodyak_id.txt"));
+    assert_eq!(vectors.len(), 56);
+
+    for vector in vectors {
+        let key = vector.bytes("Key").try_into().unwrap();
+        let cipher = XoodyakAead::new(&key)
+            .with_key_id(&vector.bytes("ID"))
+            .unwrap()
+            .with_counter(&vector.bytes("Counter"));
+        let nonce = vector.bytes("Nonce");
+        let associated_data = vector.bytes("AD");
+        let plaintext = vector.bytes("PT");
+
+        let mut buffer = plaintext.clone();
+        cipher
+            .encrypt_inplace(&nonce, &associated_data, &mut buffer)
+            .unwrap();
+        assert_eq!(
+            buffer,
+            vector.bytes("CT"),
+            "encryption of vector {}",
+            vector.count()
+        );
+
+        cipher
+            .decrypt_inplace(&nonce, &associated_data, &mut buffer)
+            .unwrap_or_else(|err| panic!("decryption of vector {}: {err}", vector.count()));
+        assert_eq!(buffer, plaintext, "decryption of vector {}", vector.count());
+    }
+}
+
+#[test]
+fn xoodyak_streaming() {
+    // Feed the associated data and the message in uneven fragments.
+    for vector in kat::parse(include_str!("data/xoodyak.txt")) {
+        let count = vector.count();
+        let cipher = XoodyakAead::new_from_slice(&vector.bytes("Key")).unwrap();
+        let nonce = vector.bytes("Nonce").try_into().unwrap();
+        let associated_data = vector.bytes("AD");
+        let plaintext = vector.bytes("PT");
+        let expected = vector.bytes("CT");
+        let (ciphertext, tag) = expected.split_at(plaintext.len());
+
+        let mut encryptor = cipher.encryptor(&nonce);
+        for chunk in associated_data.chunks(3) {
+            encryptor.update_ad(chunk).unwrap();
+        }
+        let mut buffer = plaintext.clone();
+        for chunk in buffer.chunks_mut(5) {
+            encryptor.update(chunk);
+        }
+        assert_eq!(buffer, ciphertext, "encryption of vector {count}");
+        assert_eq!(encryptor.finalize()[..], *tag, "tag of vector {count}");
+
+        let mut decryptor = cipher.decryptor(&nonce);
+        for chunk in associated_data.chunks(7) {
+            decryptor.update_ad(chunk).unwrap();
+        }
+        for chunk in buffer.chunks_mut(11) {
+            decryptor.update(chunk);
+        }
+        assert_eq!(buffer, plaintext, "decryption of vector {count}");
+        assert_eq!(decryptor.finalize(tag), Ok(()), "tag of vector {count}");
+    }
+}